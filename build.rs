@@ -2,10 +2,8 @@ use std::{env, path::PathBuf};
 
 fn main() {
     // Tell cargo to rerun this build script whenever any file in the `lib/` directory changes
-    for entry in glob::glob("lib/**/*").unwrap() {
-        if let Ok(path) = entry {
-            println!("cargo:rerun-if-changed={}", path.display());
-        }
+    for path in glob::glob("lib/**/*").unwrap().flatten() {
+        println!("cargo:rerun-if-changed={}", path.display());
     }
 
     // Tell cargo to rerun this build script whenever the wrapper header changes
@@ -0,0 +1,445 @@
+//! The command-line interface. Options that can be switched on and off have a
+//! `--x`/`--no-x` pair of flags, where the last one given wins and neither
+//! leaves the option to the environment and config file.
+
+use crate::{config::Config, printing};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "rinfo", about = "Get information about your system")]
+pub struct Cli
+{
+    /// Read this config file instead of the default one
+    #[structopt(long, parse(from_os_str), conflicts_with = "no-config")]
+    pub config: Option<PathBuf>,
+
+    /// Don't read a config file
+    #[structopt(long)]
+    pub no_config: bool,
+
+    /// Use this profile from the config file, instead of the ones matching the
+    /// system
+    #[structopt(long)]
+    pub profile: Option<String>,
+
+    #[structopt(flatten)]
+    pub flags: Flags,
+
+    #[structopt(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Command
+{
+    /// List PCI and USB devices
+    Devices
+    {
+        /// Only list devices whose class or subclass contains this (e.g. "vga",
+        /// "hub")
+        #[structopt(short, long)]
+        class: Option<String>,
+    },
+
+    /// Create, check, or print the config file
+    Config(ConfigCommand),
+}
+
+#[derive(Debug, StructOpt)]
+pub enum ConfigCommand
+{
+    /// Write a default config file, with every option commented out
+    Init
+    {
+        /// Replace the config file if it exists
+        #[structopt(short, long)]
+        force: bool,
+    },
+
+    /// Report mistakes in the config file, like options that don't exist
+    Check,
+
+    /// Print the config file's path
+    Path,
+
+    /// Print the configuration from the config file, the environment, and the
+    /// flags, merged
+    Show,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct Flags
+{
+    /// Print CPU information
+    #[structopt(long, overrides_with = "no-cpu")]
+    cpu: bool,
+
+    /// Don't print CPU information
+    #[structopt(short = "c", long, alias = "omit-cpu", overrides_with = "cpu")]
+    no_cpu: bool,
+
+    /// Print RAM information
+    #[structopt(long, overrides_with = "no-ram")]
+    ram: bool,
+
+    /// Don't print RAM information
+    #[structopt(short = "r", long, alias = "omit-ram", overrides_with = "ram")]
+    no_ram: bool,
+
+    /// Print motherboard information
+    #[structopt(long, overrides_with = "no-motherboard")]
+    motherboard: bool,
+
+    /// Don't print motherboard information
+    #[structopt(
+        short = "m",
+        long,
+        alias = "omit-motherboard",
+        overrides_with = "motherboard"
+    )]
+    no_motherboard: bool,
+
+    /// Print caller (USER, SHELL) information
+    #[structopt(long, overrides_with = "no-caller")]
+    caller: bool,
+
+    /// Don't print caller (USER, SHELL) information
+    #[structopt(short = "p", long, alias = "omit-caller", overrides_with = "caller")]
+    no_caller: bool,
+
+    /// Print the system hostname
+    #[structopt(long, overrides_with = "no-hostname")]
+    hostname: bool,
+
+    /// Don't print the system hostname
+    #[structopt(
+        short = "n",
+        long,
+        alias = "omit-hostname",
+        overrides_with = "hostname"
+    )]
+    no_hostname: bool,
+
+    /// Print operating system information
+    #[structopt(long, overrides_with = "no-os")]
+    os: bool,
+
+    /// Don't print operating system information
+    #[structopt(short = "o", long, alias = "omit-os", overrides_with = "os")]
+    no_os: bool,
+
+    /// Print character art
+    #[structopt(long, overrides_with = "no-art")]
+    art: bool,
+
+    /// Don't print character art
+    #[structopt(short = "a", long, alias = "omit-art", overrides_with = "art")]
+    no_art: bool,
+
+    /// Print local IP address
+    #[structopt(long, overrides_with = "no-ip")]
+    ip: bool,
+
+    /// Don't print local IP address
+    #[structopt(short = "i", long, alias = "omit-ip", overrides_with = "ip")]
+    no_ip: bool,
+
+    /// Print system load information
+    #[structopt(long, overrides_with = "no-load")]
+    load: bool,
+
+    /// Don't print system load information
+    #[structopt(long, alias = "omit-load", overrides_with = "load")]
+    no_load: bool,
+
+    /// Sample CPU utilisation over this many milliseconds
+    #[structopt(long)]
+    load_interval: Option<u64>,
+
+    /// Print locale, timezone, and keyboard layout information
+    #[structopt(long, overrides_with = "no-locale")]
+    locale: bool,
+
+    /// Don't print locale, timezone, and keyboard layout information
+    #[structopt(long, alias = "omit-locale", overrides_with = "locale")]
+    no_locale: bool,
+
+    /// Print logged-in users
+    #[structopt(long, overrides_with = "no-users")]
+    users: bool,
+
+    /// Don't print logged-in users
+    #[structopt(long, alias = "omit-users", overrides_with = "users")]
+    no_users: bool,
+
+    /// Print init system information
+    #[structopt(long, overrides_with = "no-init")]
+    init: bool,
+
+    /// Don't print init system information
+    #[structopt(long, alias = "omit-init", overrides_with = "init")]
+    no_init: bool,
+
+    /// Print security (SELinux, AppArmor, Secure Boot) information
+    #[structopt(long, overrides_with = "no-security")]
+    security: bool,
+
+    /// Don't print security (SELinux, AppArmor, Secure Boot) information
+    #[structopt(long, alias = "omit-security", overrides_with = "security")]
+    no_security: bool,
+
+    /// Print sound cards and sound servers
+    #[structopt(long, overrides_with = "no-audio")]
+    audio: bool,
+
+    /// Don't print sound cards and sound servers
+    #[structopt(long, alias = "omit-audio", overrides_with = "audio")]
+    no_audio: bool,
+
+    /// Print default gateways and DNS servers
+    #[structopt(long, overrides_with = "no-route")]
+    route: bool,
+
+    /// Don't print default gateways and DNS servers
+    #[structopt(long, alias = "omit-route", overrides_with = "route")]
+    no_route: bool,
+
+    /// Sample and print the throughput of each network interface
+    #[structopt(short = "t", long, overrides_with = "no-net-rate")]
+    net_rate: bool,
+
+    /// Don't sample network throughput
+    #[structopt(long, overrides_with = "net-rate")]
+    no_net_rate: bool,
+
+    /// Milliseconds to sample network throughput over
+    #[structopt(long)]
+    net_rate_interval: Option<u64>,
+
+    /// Look up and print the public (WAN) IP address
+    #[structopt(short = "w", long, overrides_with = "no-wan")]
+    wan: bool,
+
+    /// Don't look up the public (WAN) IP address
+    #[structopt(long, overrides_with = "wan")]
+    no_wan: bool,
+
+    /// The endpoint to look up the public IP address from: `http://host/path`
+    /// or `dns://server/name` (for a TXT record)
+    #[structopt(long)]
+    wan_endpoint: Option<String>,
+
+    /// Seconds to reuse a looked up public IP address for
+    #[structopt(long)]
+    wan_cache_ttl: Option<u64>,
+
+    /// Print character art above information
+    #[structopt(short = "v", long, overrides_with = "no-vertical-art")]
+    vertical_art: bool,
+
+    /// Print character art beside information
+    #[structopt(long, overrides_with = "vertical-art")]
+    no_vertical_art: bool,
+
+    /// Lay the output out for a terminal this many columns wide (defaults to
+    /// the terminal's width)
+    #[structopt(long)]
+    width: Option<usize>,
+
+    /// Vertically center the information beside the character art
+    #[structopt(long, overrides_with = "no-center")]
+    center: bool,
+
+    /// Align the information with the top of the character art
+    #[structopt(long, overrides_with = "center")]
+    no_center: bool,
+
+    /// Print the small variant of the character art
+    #[structopt(long, overrides_with = "no-small-art")]
+    small_art: bool,
+
+    /// Print the large variant of the character art
+    #[structopt(long, overrides_with = "small-art")]
+    no_small_art: bool,
+
+    /// Print this built-in logo instead of the operating system's (e.g. "arch",
+    /// "tux")
+    #[structopt(long)]
+    logo: Option<printing::OsArt>,
+
+    /// Print the art in this file, which may use `${c1}`..`${c6}` color markers
+    #[structopt(long, parse(from_os_str))]
+    logo_file: Option<PathBuf>,
+
+    /// Draw the logo as an image when the terminal supports it (kitty, iTerm2,
+    /// or sixel graphics)
+    #[structopt(long, overrides_with = "no-image")]
+    image: bool,
+
+    /// Always draw the logo as character art
+    #[structopt(long, overrides_with = "image")]
+    no_image: bool,
+
+    /// The PNG to draw instead of the distribution's logo
+    #[structopt(long, parse(from_os_str))]
+    image_file: Option<PathBuf>,
+
+    /// The graphics protocol to draw the image with, instead of detecting it:
+    /// kitty, iterm2, or sixel
+    #[structopt(long, possible_values = &["kitty", "iterm2", "sixel"])]
+    image_protocol: Option<printing::image::ImageProtocol>,
+
+    /// How many columns wide to draw the image (defaults to the art's width)
+    #[structopt(long)]
+    image_width: Option<usize>,
+
+    /// When to color the output: always, auto, or never
+    #[structopt(long, possible_values = &["always", "auto", "never"])]
+    color: Option<printing::color::ColorChoice>,
+
+    /// Mask sensitive values, like the hostname and IP addresses, so the output
+    /// can be shared
+    #[structopt(long, overrides_with = "no-redact")]
+    redact: bool,
+
+    /// Print sensitive values as they are
+    #[structopt(long, overrides_with = "redact")]
+    no_redact: bool,
+
+    /// Print the information with this template instead (e.g. "{cpu.name} @
+    /// {cpu.ghz:.1} GHz")
+    #[structopt(long)]
+    template: Option<String>,
+
+    /// The modules to print, in order (e.g. "os,cpu,memory")
+    #[structopt(long, use_delimiter = true, require_delimiter = true)]
+    modules: Option<Vec<String>>,
+}
+
+/// The state a `--x`/`--no-x` pair of flags sets, if either was given
+fn switch(on: bool, off: bool) -> Option<bool>
+{
+    match (on, off)
+    {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
+/// `value` as the value of an option in a table
+fn option<T: serde::Serialize>(value: Option<T>) -> Option<toml::Value>
+{
+    value.and_then(|value| toml::Value::try_from(value).ok())
+}
+
+impl From<Flags> for Config
+{
+    fn from(flags: Flags) -> Self
+    {
+        let options = [
+            ("load", "interval", option(flags.load_interval)),
+            (
+                "net",
+                "rate",
+                option(switch(flags.net_rate, flags.no_net_rate)),
+            ),
+            ("net", "rate_interval", option(flags.net_rate_interval)),
+            ("wan", "endpoint", option(flags.wan_endpoint)),
+            ("wan", "cache_ttl", option(flags.wan_cache_ttl)),
+        ];
+        let mut config = Self {
+            omit_cpu: switch(flags.no_cpu, flags.cpu),
+            omit_ram: switch(flags.no_ram, flags.ram),
+            omit_motherboard: switch(flags.no_motherboard, flags.motherboard),
+            omit_caller: switch(flags.no_caller, flags.caller),
+            omit_hostname: switch(flags.no_hostname, flags.hostname),
+            omit_os: switch(flags.no_os, flags.os),
+            omit_art: switch(flags.no_art, flags.art),
+            omit_ip: switch(flags.no_ip, flags.ip),
+            omit_load: switch(flags.no_load, flags.load),
+            omit_locale: switch(flags.no_locale, flags.locale),
+            omit_users: switch(flags.no_users, flags.users),
+            omit_init: switch(flags.no_init, flags.init),
+            omit_security: switch(flags.no_security, flags.security),
+            omit_audio: switch(flags.no_audio, flags.audio),
+            omit_route: switch(flags.no_route, flags.route),
+            wan: switch(flags.wan, flags.no_wan),
+            vertical_art: switch(flags.vertical_art, flags.no_vertical_art),
+            width: flags.width,
+            center: switch(flags.center, flags.no_center),
+            small_art: switch(flags.small_art, flags.no_small_art),
+            logo: flags.logo,
+            logo_file: flags.logo_file,
+            image: switch(flags.image, flags.no_image),
+            image_file: flags.image_file,
+            image_protocol: flags.image_protocol,
+            image_width: flags.image_width,
+            color: flags.color,
+            redact: switch(flags.redact, flags.no_redact),
+            template: flags.template,
+            modules: flags.modules,
+            ..Self::default()
+        };
+        for (table, key, value) in options
+        {
+            if let Some(value) = value
+            {
+                config.set_section_option(table, key, value);
+            }
+        }
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn config(args: &[&str]) -> Config
+    {
+        let cli =
+            Cli::from_iter_safe(std::iter::once("rinfo").chain(args.iter().copied())).unwrap();
+        cli.flags.into()
+    }
+
+    #[test]
+    fn flag_pairs_are_tri_state()
+    {
+        assert_eq!(config(&[]), Config::default());
+
+        assert_eq!(config(&["--art", "--no-art"]).omit_art, Some(true));
+        let config = config(&["-c", "--omit-ram", "--no-art", "--art", "--wan", "--no-wan"]);
+        assert_eq!(config.omit_cpu, Some(true));
+        assert_eq!(config.omit_ram, Some(true));
+        assert_eq!(config.omit_art, Some(false));
+        assert_eq!(config.wan, Some(false));
+        assert_eq!(config.omit_os, None);
+    }
+
+    #[test]
+    fn table_options_set_their_tables()
+    {
+        let config = config(&[
+            "--load-interval",
+            "100",
+            "-t",
+            "--wan-endpoint",
+            "dns://a/b",
+        ]);
+        assert_eq!(
+            config.sections,
+            toml::from_str(concat!(
+                "[load]\n",
+                "interval = 100\n",
+                "[net]\n",
+                "rate = true\n",
+                "[wan]\n",
+                "endpoint = \"dns://a/b\"\n",
+            ))
+            .unwrap()
+        );
+    }
+}
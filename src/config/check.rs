@@ -0,0 +1,431 @@
+//! Finding the mistakes in a config file: TOML that doesn't parse, values of
+//! the wrong type, and keys that aren't options (which would otherwise be
+//! ignored).
+
+use super::{camel_case, profile::Conditions, Config, SWITCHED_TABLES};
+use crate::{modules, printing::template::suggest};
+
+/// The default config file, with every option commented out and documented
+pub const DEFAULT: &str = include_str!("default.toml");
+
+/// A mistake in a config file
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Diagnostic
+{
+    /// The line and column (from 1) of the mistake, when it's known
+    pub position: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl std::fmt::Display for Diagnostic
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self.position
+        {
+            Some((line, column)) => write!(f, "{line}:{column}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Diagnostic
+{
+    fn from_error(source: &str, e: &toml::de::Error) -> Self
+    {
+        Self {
+            position: e.span().map(|span| position(source, span.start)),
+            message: e.message().trim().replace('\n', ": "),
+        }
+    }
+}
+
+/// Every mistake in the config file `source`. Nothing else is checked when it
+/// doesn't parse.
+pub fn check(source: &str) -> Vec<Diagnostic>
+{
+    match Config::parse(source)
+    {
+        Ok(_) => unknown_keys(source),
+        Err(e) => vec![Diagnostic::from_error(source, &e)],
+    }
+}
+
+/// The keys in the config file `source` that aren't options, and profiles
+/// whose options don't parse
+pub fn unknown_keys(source: &str) -> Vec<Diagnostic>
+{
+    let Ok(mut table) = toml::from_str::<toml::Table>(source)
+    else
+    {
+        return Vec::new();
+    };
+    let known = known();
+    let mut diagnostics = Vec::new();
+
+    let profiles = table.remove("profile");
+    unknown_in(source, &table, &known, None, &mut diagnostics);
+    match profiles
+    {
+        Some(toml::Value::Table(profiles)) =>
+        {
+            for (name, profile) in profiles
+            {
+                check_profile(source, &name, profile, &known, &mut diagnostics);
+            }
+        }
+        Some(_) =>
+        {
+            diagnostics.push(Diagnostic {
+                position: locate(source, None, "profile"),
+                message: "'profile' should be a table, e.g. [profile.server]".to_string(),
+            })
+        }
+        None => (),
+    }
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.position);
+    diagnostics
+}
+
+/// The mistakes in the `[profile.<name>]` table
+fn check_profile(
+    source: &str,
+    name: &str,
+    profile: toml::Value,
+    known: &toml::Table,
+    diagnostics: &mut Vec<Diagnostic>,
+)
+{
+    let path = format!("profile.{name}");
+    let header = locate(source, None, &path).or_else(|| locate(source, Some("profile"), name));
+    let toml::Value::Table(mut profile) = profile
+    else
+    {
+        diagnostics.push(Diagnostic {
+            position: header,
+            message: format!("'{path}' should be a table, e.g. [{path}]"),
+        });
+        return;
+    };
+
+    if let Some(conditions) = profile.remove("match")
+    {
+        let match_path = format!("{path}.match");
+        let position = |key: &str| {
+            locate(source, Some(&match_path), key)
+                .or_else(|| locate(source, Some(&path), "match"))
+                .or(header)
+        };
+        let known_conditions: Vec<&str> = known["profile"]
+            .as_table()
+            .into_iter()
+            .flat_map(|profiles| profiles.values())
+            .filter_map(|profile| profile.get("match")?.as_table())
+            .flat_map(|conditions| conditions.keys().map(String::as_str))
+            .collect();
+        let unknown_conditions: Vec<Diagnostic> = conditions
+            .as_table()
+            .into_iter()
+            .flat_map(|conditions| conditions.keys())
+            .filter(|key| !known_conditions.contains(&key.as_str()))
+            .map(|key| {
+                Diagnostic {
+                    position: position(key),
+                    message: unknown(
+                        key,
+                        Some(&match_path),
+                        suggest(key, known_conditions.iter().copied()),
+                    ),
+                }
+            })
+            .collect();
+
+        if unknown_conditions.is_empty()
+        {
+            if let Err(e) = conditions.try_into::<Conditions>()
+            {
+                diagnostics.push(Diagnostic {
+                    position: position("match"),
+                    message: format!("[{match_path}]: {}", e.message().trim()),
+                });
+            }
+        }
+        diagnostics.extend(unknown_conditions);
+    }
+
+    unknown_in(source, &profile, known, Some(&path), diagnostics);
+    if let Err(e) = Config::from_table(profile)
+    {
+        diagnostics.push(Diagnostic {
+            position: header,
+            message: format!("[{path}]: {}", e.message().trim()),
+        });
+    }
+}
+
+/// The keys in `table`, at `path` in the config file, that aren't options
+fn unknown_in(
+    source: &str,
+    table: &toml::Table,
+    known: &toml::Table,
+    path: Option<&str>,
+    diagnostics: &mut Vec<Diagnostic>,
+)
+{
+    let known_keys = || {
+        known
+            .iter()
+            .filter(|(_, value)| !value.is_table())
+            .map(|(key, _)| key.as_str())
+    };
+    let join = |key: &str| {
+        match path
+        {
+            Some(path) => format!("{path}.{key}"),
+            None => key.to_string(),
+        }
+    };
+
+    for (key, value) in table
+    {
+        let is_table = key == "theme"
+            || SWITCHED_TABLES.contains(&key.as_str())
+            || modules::find(key).is_some();
+        if !is_table
+        {
+            if known_keys().any(|known| known == key)
+            {
+                continue;
+            }
+
+            let camel = camel_case(key);
+            let suggestion = known_keys().find(|known| *known == camel).or_else(|| {
+                let tables = ["theme", "redact"]
+                    .into_iter()
+                    .chain(modules::MODULES.iter().map(|module| module.name));
+                suggest(key, known_keys().chain(tables))
+            });
+            diagnostics.push(Diagnostic {
+                position: locate(source, path, key),
+                message: unknown(key, path, suggestion),
+            });
+            continue;
+        }
+
+        let Some(options) = value.as_table()
+        else
+        {
+            // The same as `enabled` in the table
+            if SWITCHED_TABLES.contains(&key.as_str())
+            {
+                continue;
+            }
+
+            diagnostics.push(Diagnostic {
+                position: locate(source, path, key),
+                message: format!("'{key}' should be a table, e.g. [{}]", join(key)),
+            });
+            continue;
+        };
+        let known_options: Vec<&str> = known
+            .get(key)
+            .and_then(toml::Value::as_table)
+            .map(|table| table.keys().map(String::as_str).collect())
+            .unwrap_or_default();
+        let table_path = join(key);
+        for option in options.keys()
+        {
+            if !known_options.contains(&option.as_str())
+            {
+                let suggestion = suggest(option, known_options.iter().copied());
+                diagnostics.push(Diagnostic {
+                    position: locate(source, Some(&table_path), option),
+                    message: unknown(option, Some(&table_path), suggestion),
+                });
+            }
+        }
+    }
+}
+
+fn unknown(name: &str, table: Option<&str>, suggestion: Option<&str>) -> String
+{
+    let mut message = format!("Unknown option '{name}'");
+    if let Some(table) = table
+    {
+        message.push_str(&format!(" in [{table}]"));
+    }
+    if let Some(suggestion) = suggestion
+    {
+        message.push_str(&format!("; did you mean '{suggestion}'?"));
+    }
+    message
+}
+
+/// The default config file with every option uncommented
+fn known() -> toml::Table
+{
+    let uncommented: String = DEFAULT
+        .lines()
+        .map(|line| {
+            match line.strip_prefix('#')
+            {
+                Some(option) if !option.is_empty() && !option.starts_with(' ') => option,
+                _ => line,
+            }
+        })
+        .map(|line| format!("{line}\n"))
+        .collect();
+    toml::from_str(&uncommented).expect("the default config file should parse")
+}
+
+/// The line and column of the byte `offset` into `source`
+fn position(source: &str, offset: usize) -> (usize, usize)
+{
+    let before = &source[..offset.min(source.len())];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Where `key` is set, in `table` or at the top level. Keys set in other ways
+/// (e.g. dotted keys or inline tables) aren't found.
+fn locate(source: &str, table: Option<&str>, key: &str) -> Option<(usize, usize)>
+{
+    let mut current = None;
+    let mut in_string = false;
+    for (i, line) in source.lines().enumerate()
+    {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        if in_string
+        {
+            in_string =
+                (trimmed.matches("\"\"\"").count() + trimmed.matches("'''").count()) % 2 == 0;
+            continue;
+        }
+        in_string = (trimmed.matches("\"\"\"").count() + trimmed.matches("'''").count()) % 2 == 1;
+
+        if let Some(header) = trimmed.strip_prefix('[')
+        {
+            let name = header.split(']').next().unwrap_or_default().trim();
+            current = Some(name.trim_matches('"').to_string());
+            if table.is_none() && name == key
+            {
+                return Some((i + 1, indent + 1));
+            }
+            continue;
+        }
+
+        let Some((name, _)) = trimmed.split_once('=')
+        else
+        {
+            continue;
+        };
+        if current.as_deref() == table && name.trim().trim_matches('"') == key
+        {
+            return Some((i + 1, indent + 1));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn default_config_has_every_option()
+    {
+        let config = Config::from_table(known()).unwrap();
+        assert!(config.theme.label.is_some());
+        assert!(config.profiles.contains_key("server"));
+
+        // Options the default config file doesn't set are left out
+        let set = toml::Table::try_from(&config).unwrap();
+        for field in Config::FIELDS
+        {
+            let key = match *field
+            {
+                "sections" => continue,
+                "profiles" => "profile".to_string(),
+                field => camel_case(field),
+            };
+            assert!(set.contains_key(&key), "{key} isn't in default.toml");
+        }
+        for name in config.sections.keys()
+        {
+            assert!(
+                modules::find(name).is_some() || SWITCHED_TABLES.contains(&name.as_str()),
+                "[{name}] isn't a module"
+            );
+        }
+        assert_eq!(check(DEFAULT), []);
+    }
+
+    #[test]
+    fn reports_mistakes()
+    {
+        let source = concat!(
+            "omitArts = true\n",
+            "omit_cpu = true\n",
+            "template = \"\"\"\n",
+            "unit = 1\n",
+            "\"\"\"\n",
+            "\n",
+            "[memory]\n",
+            "  units = \"si\"\n",
+            "[board]\n",
+            "model = \"x\"\n",
+        );
+        let messages: Vec<String> = check(source).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "1:1: Unknown option 'omitArts'; did you mean 'omitArt'?",
+                "2:1: Unknown option 'omit_cpu'; did you mean 'omitCpu'?",
+                "8:3: Unknown option 'units' in [memory]; did you mean 'unit'?",
+                "10:1: Unknown option 'model' in [board]",
+            ]
+        );
+
+        let source = concat!(
+            "[profile.server]\n",
+            "match = { ssh = true, hostnmae = \"web-*\" }\n",
+            "omitArts = true\n",
+            "[profile.server.memory]\n",
+            "units = \"si\"\n",
+            "[profile.laptop]\n",
+            "match = { container = \"no\" }\n",
+            "width = \"wide\"\n",
+        );
+        let messages: Vec<String> = check(source).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                concat!(
+                    "2:1: Unknown option 'hostnmae' in [profile.server.match]; ",
+                    "did you mean 'hostname'?"
+                ),
+                "3:1: Unknown option 'omitArts' in [profile.server]; did you mean 'omitArt'?",
+                "5:1: Unknown option 'units' in [profile.server.memory]; did you mean 'unit'?",
+                "6:1: [profile.laptop]: invalid type: string \"wide\", expected usize",
+                "7:1: [profile.laptop.match]: invalid type: string \"no\", expected a boolean",
+            ]
+        );
+
+        let messages: Vec<String> = check("width = \"wide\"\n")
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(messages.len(), 1);
+        assert!(
+            messages[0].starts_with("1:9: invalid type"),
+            "{}",
+            messages[0]
+        );
+    }
+}
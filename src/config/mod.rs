@@ -0,0 +1,841 @@
+//! The configuration, layered from the defaults, the config file (and its
+//! profiles), `RINFO_*` environment variables, and the command-line flags, in
+//! increasing precedence.
+
+pub mod check;
+pub mod profile;
+
+use crate::{modules, printing, printing::template::suggest};
+use profile::{Conditions, System, ENV_PROFILE};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The prefix of environment variables that set options
+pub const ENV_PREFIX: &str = "RINFO_";
+
+/// The environment variable naming the config file to read
+pub const ENV_CONFIG: &str = "RINFO_CONFIG";
+
+/// Tables named after an option that switches them on, like `wan = true` and
+/// `[wan]`. In the table, the option is `enabled`.
+pub const SWITCHED_TABLES: [&str; 2] = ["wan", "redact"];
+
+/// Options that have moved into a module's table, by their old name: the old
+/// name, the table, and the option's name there
+pub const LEGACY_OPTIONS: [(&str, &str, &str); 5] = [
+    ("loadInterval", "load", "interval"),
+    ("netRate", "net", "rate"),
+    ("netRateInterval", "net", "rate_interval"),
+    ("wanEndpoint", "wan", "endpoint"),
+    ("wanCacheTtl", "wan", "cache_ttl"),
+];
+
+/// Layering one value over another
+pub trait Combine
+{
+    /// Layer `other` over `self`, so that whatever `other` sets takes
+    /// precedence
+    fn combine(&mut self, other: Self);
+}
+
+impl<T> Combine for Option<T>
+{
+    fn combine(&mut self, other: Self)
+    {
+        if other.is_some()
+        {
+            *self = other;
+        }
+    }
+}
+
+/// Tables are merged key by key, and tables in them likewise
+impl Combine for toml::Table
+{
+    fn combine(&mut self, other: Self)
+    {
+        for (key, value) in other
+        {
+            match (self.get_mut(&key), value)
+            {
+                (Some(toml::Value::Table(table)), toml::Value::Table(other)) =>
+                {
+                    table.combine(other)
+                }
+                (_, value) =>
+                {
+                    self.insert(key, value);
+                }
+            }
+        }
+    }
+}
+
+/// Declare `Config` with a `combine` that layers every one of its fields, so
+/// none can be left out, and a list of them to check the default config file
+/// against
+macro_rules! layered {
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident
+        {
+            $($(#[$field_attr:meta])* pub $field:ident: $ty:ty,)*
+        }
+    ) => {
+        $(#[$attr])*
+        pub struct $name
+        {
+            $($(#[$field_attr])* pub $field: $ty,)*
+        }
+
+        impl $name
+        {
+            /// The name of each field
+            #[cfg(test)]
+            pub const FIELDS: &'static [&'static str] = &[$(stringify!($field),)*];
+
+            /// Layer `other` over `self`, so that whatever `other` sets takes
+            /// precedence
+            pub fn combine(&mut self, other: Self) { $(self.$field.combine(other.$field);)* }
+        }
+    };
+}
+
+layered! {
+    /// One layer of configuration. Options that a layer doesn't set are `None`,
+    /// so they fall through to the layer beneath.
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+    #[serde(rename_all = "camelCase", default)]
+    pub struct Config
+    {
+        /// Don't print CPU information
+        pub omit_cpu: Option<bool>,
+
+        /// Don't print RAM information
+        pub omit_ram: Option<bool>,
+
+        /// Don't print motherboard information
+        pub omit_motherboard: Option<bool>,
+
+        /// Don't print caller (USER, SHELL) information
+        pub omit_caller: Option<bool>,
+
+        /// Don't print the system hostname
+        pub omit_hostname: Option<bool>,
+
+        /// Don't print operating system information
+        pub omit_os: Option<bool>,
+
+        /// Don't print character art
+        pub omit_art: Option<bool>,
+
+        /// Don't print local IP address
+        pub omit_ip: Option<bool>,
+
+        /// Don't print system load information
+        pub omit_load: Option<bool>,
+
+        /// Don't print locale, timezone, and keyboard layout information
+        pub omit_locale: Option<bool>,
+
+        /// Don't print logged-in users
+        pub omit_users: Option<bool>,
+
+        /// Don't print init system information
+        pub omit_init: Option<bool>,
+
+        /// Don't print security (SELinux, AppArmor, Secure Boot) information
+        pub omit_security: Option<bool>,
+
+        /// Don't print sound cards and sound servers
+        pub omit_audio: Option<bool>,
+
+        /// Don't print default gateways and DNS servers
+        pub omit_route: Option<bool>,
+
+        /// Look up and print the public (WAN) IP address
+        pub wan: Option<bool>,
+
+        /// Print character art above information
+        pub vertical_art: Option<bool>,
+
+        /// Lay the output out for a terminal this many columns wide
+        pub width: Option<usize>,
+
+        /// Vertically center the information beside the character art
+        pub center: Option<bool>,
+
+        /// Print the small variant of the character art
+        pub small_art: Option<bool>,
+
+        /// Print this built-in logo instead of the operating system's
+        pub logo: Option<printing::OsArt>,
+
+        /// Print the art in this file
+        pub logo_file: Option<PathBuf>,
+
+        /// Draw the logo as an image when the terminal supports it
+        pub image: Option<bool>,
+
+        /// The PNG to draw instead of the distribution's logo
+        pub image_file: Option<PathBuf>,
+
+        /// The graphics protocol to draw the image with, instead of detecting it
+        pub image_protocol: Option<printing::image::ImageProtocol>,
+
+        /// How many columns wide to draw the image
+        pub image_width: Option<usize>,
+
+        /// When to color the output
+        pub color: Option<printing::color::ColorChoice>,
+
+        /// Colors for labels and the character art, set with the `[theme]` table
+        #[serde(skip_serializing_if = "is_default")]
+        pub theme: printing::color::Theme,
+
+        /// Mask sensitive values, like the hostname and IP addresses, with the
+        /// rules in the `[redact]` table
+        pub redact: Option<bool>,
+
+        /// Print the information with this template instead
+        pub template: Option<String>,
+
+        /// The modules to print, in order
+        pub modules: Option<Vec<String>>,
+
+        /// Each module's `[module]` table (e.g. `[memory] unit = "si"`), which the
+        /// module reads its options from
+        #[serde(flatten)]
+        pub sections: toml::Table,
+
+        /// The `[profile.<name>]` tables, which are layered over the rest of the
+        /// config file by `apply_profiles`
+        #[serde(rename = "profile", skip_serializing_if = "is_default")]
+        pub profiles: toml::Table,
+    }
+}
+
+impl Config
+{
+    /// The options in the module's `[name]` table, or the defaults when
+    /// there's no table. An invalid table is reported, and the defaults used.
+    pub fn section<T: DeserializeOwned + Default>(&self, name: &str) -> T
+    {
+        match self.sections.get(name)
+        {
+            Some(table) =>
+            {
+                table.clone().try_into().unwrap_or_else(|e| {
+                    eprintln!("Couldn't parse the [{name}] table: {e}");
+                    T::default()
+                })
+            }
+            None => T::default(),
+        }
+    }
+
+    /// Set `key` in the `[table]` table to `value`
+    pub fn set_section_option(&mut self, table: &str, key: &str, value: toml::Value)
+    {
+        let mut section = toml::Table::new();
+        section.insert(key.to_string(), value);
+        let mut sections = toml::Table::new();
+        sections.insert(table.to_string(), toml::Value::Table(section));
+        self.sections.combine(sections);
+    }
+
+    /// Move the `LEGACY_OPTIONS` into their tables, so that a layer only has
+    /// one value for each option. What the table sets itself takes precedence.
+    fn normalise(mut self) -> Self
+    {
+        for (legacy, table, key) in LEGACY_OPTIONS
+        {
+            let Some(value) = self.sections.remove(legacy)
+            else
+            {
+                continue;
+            };
+            let set = matches!(
+                self.sections.get(table),
+                Some(toml::Value::Table(section)) if section.contains_key(key)
+            );
+            if !set
+            {
+                self.set_section_option(table, key, value);
+            }
+        }
+        self
+    }
+
+    /// Parse a config file
+    pub fn parse(source: &str) -> Result<Self, toml::de::Error>
+    {
+        // Errors only say where they are when parsed straight from `source`
+        toml::from_str(source).map(Self::normalise).or_else(|e| {
+            match toml::from_str::<toml::Table>(source)
+            {
+                Ok(table)
+                    if SWITCHED_TABLES
+                        .iter()
+                        .any(|name| matches!(table.get(*name), Some(toml::Value::Table(_)))) =>
+                {
+                    Self::from_table(table)
+                }
+                _ => Err(e),
+            }
+        })
+    }
+
+    /// A layer of configuration from `table`. The `SWITCHED_TABLES` are
+    /// moved into `sections` by hand, as serde can't tell them apart from
+    /// their options, and their `enabled` keys set the options.
+    fn from_table(mut table: toml::Table) -> Result<Self, toml::de::Error>
+    {
+        let mut sections = Vec::new();
+        for name in SWITCHED_TABLES
+        {
+            match table.remove(name)
+            {
+                Some(toml::Value::Table(mut section)) =>
+                {
+                    if let Some(enabled) = section.remove("enabled")
+                    {
+                        table.insert(name.to_string(), enabled);
+                    }
+                    if !section.is_empty()
+                    {
+                        sections.push((name, section));
+                    }
+                }
+                Some(option) =>
+                {
+                    table.insert(name.to_string(), option);
+                }
+                None => (),
+            }
+        }
+
+        let mut config: Self = toml::Value::Table(table).try_into()?;
+        for (name, section) in sections
+        {
+            config
+                .sections
+                .insert(name.to_string(), toml::Value::Table(section));
+        }
+        Ok(config.normalise())
+    }
+
+    /// The configuration as a config file
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error>
+    {
+        // A switched table replaces its option, so the option is put back as
+        // `enabled`
+        let mut table = toml::Table::try_from(self)?;
+        for (name, enabled) in [("wan", self.wan), ("redact", self.redact)]
+        {
+            if let (Some(enabled), Some(toml::Value::Table(section))) =
+                (enabled, table.get_mut(name))
+            {
+                section.insert("enabled".to_string(), toml::Value::Boolean(enabled));
+            }
+        }
+        toml::to_string_pretty(&table)
+    }
+
+    /// Layer the config file, the environment, and `flags`. The config file is
+    /// `path`, or else `$RINFO_CONFIG` or the default one, unless `no_config`,
+    /// and its profiles are the one called `profile` (or `$RINFO_PROFILE`), or
+    /// else the ones the system matches.
+    pub fn load(
+        path: Option<PathBuf>,
+        no_config: bool,
+        profile: Option<String>,
+        flags: Self,
+    ) -> Self
+    {
+        let mut config = match Self::path(path, no_config)
+        {
+            Some((path, required)) =>
+            {
+                Self::from_file(&path, required).unwrap_or_else(|e| {
+                    eprintln!("{e}");
+                    if required
+                    {
+                        std::process::exit(66);
+                    }
+                    Self::default()
+                })
+            }
+            None => Self::default(),
+        };
+        let profile = profile.or_else(|| std::env::var(ENV_PROFILE).ok());
+        config.apply_profiles(profile.as_deref(), System::detect);
+        config.combine(Self::from_env(std::env::vars()));
+        config.combine(flags);
+        config
+    }
+
+    /// Layer the `[profile.<name>]` tables over the rest of the config: the
+    /// profile called `chosen`, or else each profile whose `match` table the
+    /// system matches, in alphabetical order. Profiles without a `match` table
+    /// are only used when chosen.
+    pub fn apply_profiles(&mut self, chosen: Option<&str>, system: impl FnOnce() -> System)
+    {
+        let mut profiles = Vec::new();
+        for (name, profile) in std::mem::take(&mut self.profiles)
+        {
+            let toml::Value::Table(mut table) = profile
+            else
+            {
+                eprintln!("Couldn't parse the [profile.{name}] table: it isn't a table");
+                continue;
+            };
+            let conditions = match table.remove("match").map(toml::Value::try_into)
+            {
+                Some(Ok(conditions)) => conditions,
+                Some(Err(e)) =>
+                {
+                    eprintln!("Couldn't parse the [profile.{name}.match] table: {e}");
+                    continue;
+                }
+                None => Conditions::default(),
+            };
+            match Self::from_table(table)
+            {
+                Ok(config) => profiles.push((name, conditions, config)),
+                Err(e) => eprintln!("Couldn't parse the [profile.{name}] table: {e}"),
+            }
+        }
+
+        if let Some(chosen) = chosen
+        {
+            match profiles.iter().position(|(name, _, _)| name == chosen)
+            {
+                Some(i) => self.combine(profiles.swap_remove(i).2),
+                None =>
+                {
+                    let names = profiles.iter().map(|(name, _, _)| name.as_str());
+                    match suggest(chosen, names)
+                    {
+                        Some(suggestion) =>
+                        {
+                            eprintln!("Unknown profile '{chosen}'; did you mean '{suggestion}'?")
+                        }
+                        None => eprintln!("Unknown profile '{chosen}'"),
+                    }
+                }
+            }
+            return;
+        }
+
+        // Finding out about the system takes a moment, so it's only done when
+        // there's something to match
+        if profiles
+            .iter()
+            .all(|(_, conditions, _)| conditions.is_empty())
+        {
+            return;
+        }
+        let system = system();
+        for (_, conditions, config) in profiles
+        {
+            if !conditions.is_empty() && conditions.matches(&system)
+            {
+                self.combine(config);
+            }
+        }
+    }
+
+    /// The config file to read, and whether it's required to exist: `path`,
+    /// or else `$RINFO_CONFIG` or the default one, unless `no_config`
+    pub fn path(path: Option<PathBuf>, no_config: bool) -> Option<(PathBuf, bool)>
+    {
+        match path
+        {
+            _ if no_config => None,
+            Some(path) => Some((path, true)),
+            None =>
+            {
+                match std::env::var_os(ENV_CONFIG)
+                {
+                    Some(path) => Some((PathBuf::from(path), true)),
+                    None =>
+                    {
+                        Self::migrate();
+                        Self::default_path().map(|path| (path, false))
+                    }
+                }
+            }
+        }
+    }
+
+    /// The default config file's path. A config file left at the old default
+    /// path, which couldn't be moved, is read from there.
+    pub fn default_path() -> Option<PathBuf>
+    {
+        let path = dirs::config_dir()?.join("rinfo").join("rinfo.toml");
+        match Self::old_default_path()
+        {
+            Some(old) if !path.exists() && old.exists() => Some(old),
+            _ => Some(path),
+        }
+    }
+
+    /// Where the default config file used to be
+    fn old_default_path() -> Option<PathBuf>
+    {
+        Some(dirs::config_dir()?.join("SBII").join("rinfo.toml"))
+    }
+
+    /// Move a config file at the old default path to the new one. A file that
+    /// can't be moved is left where it is.
+    fn migrate()
+    {
+        let (Some(old), Some(dir)) = (Self::old_default_path(), dirs::config_dir())
+        else
+        {
+            return;
+        };
+        let new = dir.join("rinfo").join("rinfo.toml");
+        if new.exists() || !old.exists()
+        {
+            return;
+        }
+
+        let moved = std::fs::create_dir_all(dir.join("rinfo")).and_then(|_| {
+            std::fs::rename(&old, &new).or_else(|_| {
+                // Across filesystems
+                if let Err(e) = std::fs::copy(&old, &new)
+                {
+                    let _ = std::fs::remove_file(&new);
+                    return Err(e);
+                }
+                std::fs::remove_file(&old)
+            })
+        });
+        if moved.is_ok()
+        {
+            // Only removed when it's empty
+            let _ = std::fs::remove_dir(dir.join("SBII"));
+            eprintln!(
+                "Moved config file '{}' to '{}'",
+                old.display(),
+                new.display()
+            );
+        }
+    }
+
+    /// Read the config file at `path`. A file that doesn't exist is an empty
+    /// config, unless `required`.
+    pub fn from_file(path: &Path, required: bool) -> Result<Self, String>
+    {
+        match std::fs::read_to_string(path)
+        {
+            Ok(contents) =>
+            {
+                let config = Self::parse(&contents)
+                    .map_err(|e| format!("Couldn't parse config file '{}': {e}", path.display()))?;
+                for diagnostic in check::unknown_keys(&contents)
+                {
+                    eprintln!("{}:{diagnostic}", path.display());
+                }
+                Ok(config)
+            }
+            Err(e) if required || e.kind() != std::io::ErrorKind::NotFound =>
+            {
+                Err(format!(
+                    "Couldn't read config file '{}': {e}",
+                    path.display()
+                ))
+            }
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// The options set by `RINFO_*` environment variables, e.g.
+    /// `RINFO_OMIT_ART=true` for `omitArt`, `RINFO_MODULES=os,cpu`, or
+    /// `RINFO_MEMORY_UNIT=si` for `unit` in the `[memory]` table. Variables
+    /// that don't name an option, or whose value doesn't fit it, are
+    /// reported and ignored.
+    pub fn from_env(vars: impl IntoIterator<Item = (String, String)>) -> Self
+    {
+        let mut config = Self::default();
+        for (var, value) in vars
+        {
+            let Some(name) = var.strip_prefix(ENV_PREFIX)
+            else
+            {
+                continue;
+            };
+            if var == ENV_CONFIG || var == ENV_PROFILE
+            {
+                continue;
+            }
+
+            match Self::from_env_var(name, &value)
+            {
+                Some(layer) => config.combine(layer),
+                None =>
+                {
+                    eprintln!("Ignoring {var}: it isn't an option, or '{value}' isn't valid for it")
+                }
+            }
+        }
+        config
+    }
+
+    /// The option `RINFO_{name}` sets to `value`. As environment variables are
+    /// untyped, `value` is tried as each type an option could have.
+    fn from_env_var(name: &str, value: &str) -> Option<Self>
+    {
+        let name = name.to_lowercase();
+        let section = name
+            .split_once('_')
+            .filter(|(table, _)| modules::find(table).is_some() || SWITCHED_TABLES.contains(table));
+
+        // The most specific types first, so e.g. `1` is a boolean for an option
+        // that is one, and a number rather than a string for the rest
+        let mut values = Vec::new();
+        match value.to_lowercase().as_str()
+        {
+            "true" | "yes" | "on" | "1" => values.push(toml::Value::Boolean(true)),
+            "false" | "no" | "off" | "0" => values.push(toml::Value::Boolean(false)),
+            _ => (),
+        }
+        if let Ok(n) = value.parse()
+        {
+            values.push(toml::Value::Integer(n));
+        }
+        values.push(toml::Value::String(value.to_string()));
+        values.push(toml::Value::Array(
+            value
+                .split(',')
+                .map(|item| toml::Value::String(item.trim().to_string()))
+                .collect(),
+        ));
+
+        values.into_iter().find_map(|value| {
+            let mut table = toml::Table::new();
+            match section
+            {
+                Some((name, key)) =>
+                {
+                    let mut section = toml::Table::new();
+                    section.insert(key.to_string(), value);
+                    check_section(name, section.clone()).ok()?;
+                    table.insert(name.to_string(), toml::Value::Table(section));
+                }
+                None =>
+                {
+                    table.insert(camel_case(&name), value);
+                }
+            }
+
+            // Keys that aren't options end up in `sections`
+            let layer = Self::from_table(table).ok()?;
+            (section.is_some() || layer.sections.is_empty()).then_some(layer)
+        })
+    }
+}
+
+/// Check that `section` fits the options of the `[name]` table
+fn check_section(name: &str, section: toml::Table) -> Result<(), toml::de::Error>
+{
+    match modules::find(name)
+    {
+        Some(module) => (module.options)(section),
+        None if name == "redact" =>
+        {
+            toml::Value::Table(section)
+                .try_into::<printing::redact::Rules>()
+                .map(drop)
+        }
+        None => Ok(()),
+    }
+}
+
+fn is_default<T: Default + PartialEq>(value: &T) -> bool { *value == T::default() }
+
+/// `snake_case` as `camelCase`
+fn camel_case(s: &str) -> String
+{
+    let mut words = s.split('_');
+    let first = words.next().unwrap_or_default().to_string();
+    words.fold(first, |mut camel, word| {
+        let mut chars = word.chars();
+        if let Some(c) = chars.next()
+        {
+            camel.extend(c.to_uppercase());
+            camel.push_str(chars.as_str());
+        }
+        camel
+    })
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)>
+    {
+        vars.iter()
+            .map(|(var, value)| (var.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn later_layers_take_precedence()
+    {
+        let mut config: Config =
+            toml::from_str("omitArt = true\nomitCpu = true\nwidth = 80").unwrap();
+        config.combine(Config::from_env(vars(&[("RINFO_WIDTH", "100")])));
+        config.combine(Config {
+            omit_art: Some(false),
+            ..Config::default()
+        });
+
+        assert_eq!(config.omit_art, Some(false));
+        assert_eq!(config.omit_cpu, Some(true));
+        assert_eq!(config.width, Some(100));
+        assert_eq!(config.omit_ram, None);
+
+        config.combine(Config {
+            omit_ram: Some(true),
+            ..Config::default()
+        });
+        assert_eq!(config.omit_ram, Some(true));
+    }
+
+    #[test]
+    fn reads_environment_variables()
+    {
+        let config = Config::from_env(vars(&[
+            ("RINFO_OMIT_ART", "yes"),
+            ("RINFO_TEMPLATE", "1"),
+            ("RINFO_MODULES", "os, cpu"),
+            ("RINFO_LOGO", "arch"),
+            ("RINFO_MEMORY_UNIT", "si"),
+            ("RINFO_LOAD_INTERVAL", "1"),
+            ("RINFO_NET_RATE", "1"),
+            ("RINFO_REDACT_MAC", "0"),
+            ("RINFO_BOARD_UNIT", "si"),
+            ("RINFO_CONFIG", "/dev/null"),
+            ("RINFO_WIDTH", "wide"),
+            ("RINFO_NOT_AN_OPTION", "1"),
+            ("HOME", "/root"),
+        ]));
+
+        let mut expected = Config {
+            omit_art: Some(true),
+            template: Some("1".to_string()),
+            modules: Some(vec!["os".to_string(), "cpu".to_string()]),
+            logo: Some(printing::OsArt::ArchLinux),
+            ..Config::default()
+        };
+        expected.sections = toml::from_str(concat!(
+            "[memory]\n",
+            "unit = \"si\"\n",
+            "[load]\n",
+            "interval = 1\n",
+            "[net]\n",
+            "rate = true\n",
+            "[redact]\n",
+            "mac = false\n",
+        ))
+        .unwrap();
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn moves_legacy_options_into_their_tables()
+    {
+        let mut config = Config::parse(concat!(
+            "loadInterval = 100\n",
+            "wanCacheTtl = 60\n",
+            "[wan]\n",
+            "cache_ttl = 30\n",
+        ))
+        .unwrap();
+        assert_eq!(
+            config.sections,
+            toml::from_str("[load]\ninterval = 100\n[wan]\ncache_ttl = 30").unwrap()
+        );
+
+        config.combine(Config::from_env(vars(&[("RINFO_LOAD_INTERVAL", "500")])));
+        assert_eq!(config.sections["load"]["interval"].as_integer(), Some(500));
+    }
+
+    #[test]
+    fn reads_the_wan_table()
+    {
+        let config = Config::parse("wan = true\nwidth = 80").unwrap();
+        assert_eq!(config.wan, Some(true));
+
+        let config = Config::parse(concat!(
+            "width = 80\n",
+            "[wan]\n",
+            "enabled = false\n",
+            "cache_ttl = 60\n",
+        ))
+        .unwrap();
+        assert_eq!(config.wan, Some(false));
+        assert_eq!(config.width, Some(80));
+        assert_eq!(
+            config.sections,
+            toml::from_str("[wan]\ncache_ttl = 60").unwrap()
+        );
+        assert_eq!(Config::parse(&config.to_toml().unwrap()).unwrap(), config);
+
+        let config = Config::from_env(vars(&[("RINFO_WAN_ENDPOINT", "http://a/")]));
+        assert_eq!(
+            config.sections,
+            toml::from_str("[wan]\nendpoint = \"http://a/\"").unwrap()
+        );
+    }
+
+    #[test]
+    fn layers_profiles()
+    {
+        let source = concat!(
+            "omitArt = false\n",
+            "width = 80\n",
+            "[profile.server]\n",
+            "match = { ssh = true }\n",
+            "omitArt = true\n",
+            "[profile.server.memory]\n",
+            "unit = \"si\"\n",
+            "[profile.tiny]\n",
+            "width = 40\n",
+            "[profile.web]\n",
+            "match = { hostname = \"web-*\", container = true }\n",
+            "width = 100\n",
+        );
+        let system = || {
+            System {
+                hostname: Some("web-01".to_string()),
+                ssh: true,
+                ..System::default()
+            }
+        };
+
+        let mut config = Config::parse(source).unwrap();
+        config.apply_profiles(None, system);
+        assert_eq!(config.omit_art, Some(true));
+        assert_eq!(config.width, Some(80));
+        assert_eq!(
+            config.sections,
+            toml::from_str("[memory]\nunit = \"si\"").unwrap()
+        );
+        assert!(config.profiles.is_empty());
+
+        let mut config = Config::parse(source).unwrap();
+        config.apply_profiles(Some("tiny"), || -> System { unreachable!() });
+        assert_eq!(config.omit_art, Some(false));
+        assert_eq!(config.width, Some(40));
+
+        let mut config = Config::parse("[profile.tiny]\nwidth = 40").unwrap();
+        config.apply_profiles(None, || -> System { unreachable!() });
+        assert_eq!(config.width, None);
+    }
+}
@@ -0,0 +1,140 @@
+//! Profiles: `[profile.<name>]` tables of options that are layered over the
+//! rest of the config file, when they're chosen with `--profile` or when the
+//! system matches their `match` table. One config file can then be shared by
+//! e.g. laptops, servers, and containers.
+
+use crate::info::{system, Host, Information, OsKind};
+use serde::Deserialize;
+
+/// The environment variable choosing a profile
+pub const ENV_PROFILE: &str = "RINFO_PROFILE";
+
+/// A profile's `match` table. Every condition it sets has to hold for the
+/// profile to be used.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Conditions
+{
+    /// The hostname, where `*` matches any characters (e.g. `"web-*"`)
+    pub hostname: Option<String>,
+
+    /// The operating system: `linux`, `windows`, `macos`, or `freebsd`
+    pub os: Option<String>,
+
+    /// Is rinfo run over SSH?
+    pub ssh: Option<bool>,
+
+    /// Is rinfo run in a container?
+    pub container: Option<bool>,
+}
+
+impl Conditions
+{
+    /// Are there no conditions, so the profile is only used when chosen?
+    pub fn is_empty(&self) -> bool { *self == Self::default() }
+
+    /// Does `system` meet every condition?
+    pub fn matches(&self, system: &System) -> bool
+    {
+        let hostname = match (&self.hostname, &system.hostname)
+        {
+            (Some(pattern), Some(hostname)) => glob(pattern, hostname),
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+        let os = match &self.os
+        {
+            Some(os) => os.eq_ignore_ascii_case(&system.os.to_string()),
+            None => true,
+        };
+
+        hostname
+            && os
+            && self.ssh.unwrap_or(system.ssh) == system.ssh
+            && self.container.unwrap_or(system.container) == system.container
+    }
+}
+
+/// What profiles' conditions are matched against
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct System
+{
+    pub hostname: Option<String>,
+    pub os: OsKind,
+    pub ssh: bool,
+    pub container: bool,
+}
+
+impl System
+{
+    /// Find out about the system rinfo is running on
+    pub fn detect() -> Self
+    {
+        Self {
+            hostname: Host::read().ok().map(|host| host.hostname),
+            os: OsKind::read().unwrap_or_default(),
+            ssh: ["SSH_CONNECTION", "SSH_CLIENT", "SSH_TTY"]
+                .iter()
+                .any(|var| std::env::var_os(var).is_some()),
+            container: system::in_container(),
+        }
+    }
+}
+
+/// Does `text` match `pattern`, where `*` matches any characters? Letters'
+/// case is ignored, as it is in hostnames.
+fn glob(pattern: &str, text: &str) -> bool
+{
+    let (pattern, text) = (pattern.to_lowercase(), text.to_lowercase());
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first)
+    else
+    {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last()
+    else
+    {
+        // No `*`
+        return rest.is_empty();
+    };
+    for part in middle
+    {
+        match rest.find(part)
+        {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn matches_conditions()
+    {
+        let system = System {
+            hostname: Some("Web-01.example.com".to_string()),
+            os: OsKind::Linux,
+            ssh: true,
+            container: false,
+        };
+        let conditions = |source: &str| -> Conditions { toml::from_str(source).unwrap() };
+
+        assert!(conditions("").matches(&system));
+        assert!(conditions("hostname = \"web-*\"\nos = \"Linux\"\nssh = true").matches(&system));
+        assert!(conditions("hostname = \"*.example.*\"").matches(&system));
+        assert!(!conditions("hostname = \"web-*\"\ncontainer = true").matches(&system));
+        assert!(!conditions("hostname = \"web\"").matches(&system));
+        assert!(!conditions("hostname = \"*.com.*\"").matches(&system));
+        assert!(!conditions("os = \"macos\"").matches(&system));
+        assert!(toml::from_str::<Conditions>("laptop = true").is_err());
+    }
+}
@@ -1,4 +1,5 @@
 /// Convert an IPV4 string to a big-endian u32
+#[cfg_attr(target_os = "linux", allow(dead_code))]
 pub fn ipv4_to_int(s: &str) -> u32
 {
     let mut octets = s.split('.').filter_map(|octet| {
@@ -23,7 +24,7 @@ pub fn ipv4_to_int(s: &str) -> u32
     //
     // Example final u32:
     // 11000000_10101000_00000001_00010011
-    // 
+    //
     // To get this we shift everything into place then add everything.
     let a = octets.next().unwrap_or_default() << 24;
     let b = octets.next().unwrap_or_default() << 16;
@@ -37,11 +38,11 @@ pub fn ipv4_to_int(s: &str) -> u32
 pub fn int_to_ipv4(i: u32) -> String
 {
     let i = i.to_le();
-    
+
     // Example source:
     // 11000000_10101000_00000001_00010011
     //
-    // Example masks: 
+    // Example masks:
     // 00000000_00000000_00000000_11111111 : octet 1
     // 00000000_00000000_11111111_00000000 : octet 2
     // 00000000_11111111_00000000_00000000 : octet 3
@@ -57,14 +58,14 @@ pub fn int_to_ipv4(i: u32) -> String
     format!(
         "{}.{}.{}.{}",
         i & 0xFF,
-        (i >> 08) & 0xFF,
+        (i >> 8) & 0xFF,
         (i >> 16) & 0xFF,
         i >> 24,
     )
-    
 }
 /// An iterator that produces only the unique elements from the iterator it was
 /// called on
+#[allow(dead_code)]
 struct Unique<'a, I>
 where
     I: Iterator<Item = &'a str>,
@@ -95,6 +96,7 @@ where
 }
 
 /// An extension trait that adds a `unique` method to the `Iterator` trait
+#[allow(dead_code)]
 trait UniqueIterator<'a>: Iterator<Item = &'a str>
 {
     fn unique(self) -> Unique<'a, Self>
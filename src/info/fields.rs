@@ -0,0 +1,480 @@
+//! Named fields of the information, for output templates to reference.
+
+use super::*;
+
+/// The value of a field
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value
+{
+    Text(String),
+    Integer(i128),
+    Float(f64),
+    Bool(bool),
+    List(Vec<Value>),
+    /// A nested structure's fields
+    Map(Vec<(&'static str, Value)>),
+    /// An optional field that isn't available
+    None,
+}
+
+impl Value
+{
+    /// The fields of `info` as a nested value
+    pub fn of(info: &impl Fields) -> Self { Value::Map(info.fields()) }
+}
+
+impl std::fmt::Display for Value
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            Value::Text(s) => write!(f, "{s}"),
+            Value::Integer(n) => write!(f, "{n}"),
+            Value::Float(n) => write!(f, "{n:.2}"),
+            Value::Bool(b) => write!(f, "{}", if *b { "yes" } else { "no" }),
+            Value::List(items) =>
+            {
+                let items: Vec<String> = items.iter().map(Value::to_string).collect();
+                write!(f, "{}", items.join(", "))
+            }
+            Value::Map(fields) =>
+            {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .filter(|(_, value)| *value != Value::None)
+                    .map(|(name, value)| format!("{name}: {value}"))
+                    .collect();
+                write!(f, "{}", fields.join(", "))
+            }
+            Value::None => Ok(()),
+        }
+    }
+}
+
+impl From<String> for Value
+{
+    fn from(s: String) -> Self { Value::Text(s) }
+}
+
+impl From<&str> for Value
+{
+    fn from(s: &str) -> Self { Value::Text(s.to_string()) }
+}
+
+impl From<f64> for Value
+{
+    fn from(n: f64) -> Self { Value::Float(n) }
+}
+
+impl From<bool> for Value
+{
+    fn from(b: bool) -> Self { Value::Bool(b) }
+}
+
+impl From<IpAddr> for Value
+{
+    fn from(address: IpAddr) -> Self { Value::Text(address.to_string()) }
+}
+
+macro_rules! integer_values {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Value
+            {
+                fn from(n: $t) -> Self { Value::Integer(n as i128) }
+            }
+        )*
+    };
+}
+integer_values!(u8, u16, u32, u64, u128, usize, i32, i64, i128);
+
+impl<T: Into<Value>> From<Option<T>> for Value
+{
+    fn from(value: Option<T>) -> Self { value.map_or(Value::None, Into::into) }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value
+{
+    fn from(items: Vec<T>) -> Self { Value::List(items.into_iter().map(Into::into).collect()) }
+}
+
+/// Information whose fields can be referenced by name
+pub trait Fields
+{
+    /// Every field, in the order they're documented
+    fn fields(&self) -> Vec<(&'static str, Value)>;
+
+    /// The text fields that can be rewritten in place (e.g. to mask them), by
+    /// their dot-separated paths. A path into a list comes up once for each
+    /// item. Only the fields something rewrites are listed.
+    fn text_fields_mut(&mut self) -> Vec<(&'static str, &mut String)> { Vec::new() }
+}
+
+impl Fields for Cpu
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("name", self.name.clone().into()),
+            ("ghz", (self.clock_rate / 1000.0).into()),
+            ("mhz", self.clock_rate.into()),
+            ("cores", self.cores.into()),
+            ("threads", self.threads.into()),
+            ("limit", self.limit.into()),
+            ("temperature", self.temperature.into()),
+            ("uptime", self.uptime.into()),
+        ]
+    }
+}
+
+impl Fields for Memory
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("total", self.total.into()),
+            ("used", self.used.into()),
+            ("available", self.available.into()),
+            ("limit", self.limit.as_ref().map(Value::of).into()),
+        ]
+    }
+}
+
+impl Fields for MemoryLimit
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![("total", self.total.into()), ("used", self.used.into())]
+    }
+}
+
+impl Fields for Load
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("average", self.average.to_vec().into()),
+            ("running", self.running.into()),
+            ("total", self.total.into()),
+            ("cpu_usage", self.cpu_usage.map(Value::from).into()),
+            ("pressure", self.pressure.as_ref().map(Value::of).into()),
+        ]
+    }
+}
+
+impl Fields for Pressure
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("cpu", self.cpu.as_ref().map(Value::of).into()),
+            ("memory", self.memory.as_ref().map(Value::of).into()),
+            ("io", self.io.as_ref().map(Value::of).into()),
+        ]
+    }
+}
+
+impl Fields for PressureStall
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("avg10", self.avg10.into()),
+            ("avg60", self.avg60.into()),
+            ("avg300", self.avg300.into()),
+        ]
+    }
+}
+
+impl Fields for BaseBoard
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("model", self.model.clone().into()),
+            ("vendor", self.vendor.clone().into()),
+        ]
+    }
+}
+
+impl Fields for Audio
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            (
+                "cards",
+                Value::List(self.cards.iter().map(Value::of).collect()),
+            ),
+            (
+                "servers",
+                Value::List(
+                    self.servers
+                        .iter()
+                        .map(|server| server.to_string().into())
+                        .collect(),
+                ),
+            ),
+        ]
+    }
+}
+
+impl Fields for SoundCard
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("index", self.index.into()),
+            ("id", self.id.clone().into()),
+            ("name", self.name.clone().into()),
+        ]
+    }
+}
+
+impl Fields for Net
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("local_ip", self.local_ip.clone().into()),
+            ("wireless", self.wireless.as_ref().map(Value::of).into()),
+            (
+                "bluetooth",
+                Value::List(self.bluetooth.iter().map(Value::of).collect()),
+            ),
+            (
+                "throughput",
+                Value::List(self.throughput.iter().map(Value::of).collect()),
+            ),
+        ]
+    }
+
+    fn text_fields_mut(&mut self) -> Vec<(&'static str, &mut String)>
+    {
+        let ssid = self
+            .wireless
+            .as_mut()
+            .and_then(|wireless| wireless.ssid.as_mut());
+        ssid.map(|ssid| ("wireless.ssid", ssid))
+            .into_iter()
+            .collect()
+    }
+}
+
+impl Fields for Wireless
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("interface", self.interface.clone().into()),
+            ("ssid", self.ssid.clone().into()),
+            ("frequency", self.frequency.into()),
+            ("link_quality", self.link_quality.into()),
+            ("signal", self.signal.into()),
+        ]
+    }
+}
+
+impl Fields for BluetoothAdapter
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("name", self.name.clone().into()),
+            ("powered", self.powered.into()),
+        ]
+    }
+}
+
+impl Fields for Throughput
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("interface", self.interface.clone().into()),
+            ("rx_rate", self.rx_rate.into()),
+            ("tx_rate", self.tx_rate.into()),
+            ("rx_total", self.rx_total.into()),
+            ("tx_total", self.tx_total.into()),
+        ]
+    }
+}
+
+impl Fields for Wan
+{
+    fn fields(&self) -> Vec<(&'static str, Value)> { vec![("address", self.address.into())] }
+}
+
+impl Fields for Route
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            (
+                "gateways",
+                Value::List(self.gateways.iter().map(Value::of).collect()),
+            ),
+            ("nameservers", self.nameservers.clone().into()),
+            ("search", self.search.clone().into()),
+        ]
+    }
+}
+
+impl Fields for Gateway
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("interface", self.interface.clone().into()),
+            ("address", self.address.into()),
+            ("metric", self.metric.into()),
+        ]
+    }
+}
+
+impl Fields for Host
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![("hostname", self.hostname.clone().into())]
+    }
+
+    fn text_fields_mut(&mut self) -> Vec<(&'static str, &mut String)>
+    {
+        vec![("hostname", &mut self.hostname)]
+    }
+}
+
+impl Fields for Caller
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("name", self.name.clone().into()),
+            ("shell", self.shell.clone().into()),
+        ]
+    }
+
+    fn text_fields_mut(&mut self) -> Vec<(&'static str, &mut String)>
+    {
+        vec![("name", &mut self.name)]
+    }
+}
+
+impl Fields for Users
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("count", self.sessions.len().into()),
+            (
+                "sessions",
+                Value::List(self.sessions.iter().map(Value::of).collect()),
+            ),
+        ]
+    }
+
+    fn text_fields_mut(&mut self) -> Vec<(&'static str, &mut String)>
+    {
+        let mut fields = Vec::new();
+        for session in &mut self.sessions
+        {
+            fields.push(("sessions.user", &mut session.user));
+            if let Some(host) = &mut session.host
+            {
+                fields.push(("sessions.host", host));
+            }
+        }
+        fields
+    }
+}
+
+impl Fields for Session
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("user", self.user.clone().into()),
+            ("tty", self.tty.clone().into()),
+            ("host", self.host.clone().into()),
+            ("login_time", self.login_time.into()),
+        ]
+    }
+}
+
+impl Fields for OperatingSystem
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("name", self.name.clone().into()),
+            ("kind", self.kind.to_string().into()),
+        ]
+    }
+}
+
+impl Fields for Init
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        vec![
+            ("kind", self.kind.to_string().into()),
+            ("state", self.state.clone().into()),
+            ("failed_units", self.failed_units.into()),
+        ]
+    }
+}
+
+impl Fields for Security
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        let vulnerabilities = self
+            .vulnerabilities
+            .iter()
+            .map(|(name, status)| {
+                Value::Map(vec![
+                    ("name", name.clone().into()),
+                    ("status", status.clone().into()),
+                ])
+            })
+            .collect();
+
+        vec![
+            ("selinux", self.selinux.clone().into()),
+            ("apparmor", self.apparmor.into()),
+            ("apparmor_profiles", self.apparmor_profiles.into()),
+            ("lockdown", self.lockdown.clone().into()),
+            ("boot_mode", self.boot_mode.to_string().into()),
+            ("secure_boot", self.secure_boot.into()),
+            ("vulnerabilities", Value::List(vulnerabilities)),
+        ]
+    }
+}
+
+impl Fields for Locale
+{
+    fn fields(&self) -> Vec<(&'static str, Value)>
+    {
+        let categories = self
+            .categories
+            .iter()
+            .map(|(name, value)| {
+                Value::Map(vec![
+                    ("name", name.clone().into()),
+                    ("value", value.clone().into()),
+                ])
+            })
+            .collect();
+
+        vec![
+            ("lang", self.lang.clone().into()),
+            ("categories", Value::List(categories)),
+            ("timezone", self.timezone.clone().into()),
+            ("utc_offset", self.utc_offset.clone().into()),
+            ("console_keymap", self.console_keymap.clone().into()),
+            ("x11_layout", self.x11_layout.clone().into()),
+        ]
+    }
+}
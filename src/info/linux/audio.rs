@@ -0,0 +1,144 @@
+use super::*;
+use std::path::Path;
+
+pub fn audio_info() -> Result<Audio, InfoError>
+{
+    let mut cards = String::new();
+    let cards = match File::open(PROC_ASOUND_CARDS).and_then(|mut f| f.read_to_string(&mut cards))
+    {
+        Ok(_) => parse_asound_cards(&cards),
+        // Without procfs ALSA support, sysfs still lists the cards
+        Err(_) => sysfs_sound_cards(),
+    };
+
+    Ok(Audio {
+        cards,
+        servers: sound_servers(),
+    })
+}
+
+/// Parse `/proc/asound/cards`. Each card is two lines, the first being e.g.
+/// ` 0 [PCH            ]: HDA-Intel - HDA Intel PCH`
+fn parse_asound_cards(cards: &str) -> Vec<SoundCard>
+{
+    cards
+        .lines()
+        .filter_map(|line| {
+            let (index, rest) = line.trim_start().split_once(' ')?;
+            let index = index.parse().ok()?;
+            let (id, rest) = rest.trim_start().strip_prefix('[')?.split_once(']')?;
+            let (_driver, name) = rest.split_once(" - ")?;
+
+            Some(SoundCard {
+                index,
+                id: id.trim().to_string(),
+                name: name.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// List the sound cards in `/sys/class/sound`, which only knows their IDs
+fn sysfs_sound_cards() -> Vec<SoundCard>
+{
+    let mut cards: Vec<SoundCard> = std::fs::read_dir(SYS_CLASS_SOUND)
+        .map(|dir| {
+            dir.flatten()
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    let index = name.strip_prefix("card")?.parse().ok()?;
+
+                    let mut id = String::new();
+                    File::open(entry.path().join("id"))
+                        .and_then(|mut f| f.read_to_string(&mut id))
+                        .ok()?;
+                    let id = id.trim().to_string();
+
+                    Some(SoundCard {
+                        index,
+                        name: id.clone(),
+                        id,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    cards.sort();
+    cards
+}
+
+/// Detect the running sound servers from their sockets in `$XDG_RUNTIME_DIR`
+/// and their process names
+fn sound_servers() -> Vec<SoundServer>
+{
+    let processes = process_names();
+    let running = |names: &[&str]| {
+        processes
+            .iter()
+            .any(|process| names.contains(&process.as_str()))
+    };
+    let runtime_dir = env::var("XDG_RUNTIME_DIR").ok().map(PathBuf::from);
+    let socket = |name: &str| {
+        runtime_dir
+            .as_ref()
+            .map(|dir| dir.join(name).exists())
+            .unwrap_or(false)
+    };
+
+    let mut servers = Vec::new();
+
+    let pipewire = socket("pipewire-0") || running(&["pipewire"]);
+    if pipewire
+    {
+        servers.push(SoundServer::PipeWire);
+    }
+
+    // PipeWire provides the PulseAudio socket too, through `pipewire-pulse`
+    if running(&["pulseaudio"]) || (socket("pulse/native") && !pipewire)
+    {
+        servers.push(SoundServer::PulseAudio);
+    }
+
+    if running(&["jackd", "jackdbus"]) || jack_shm()
+    {
+        servers.push(SoundServer::Jack);
+    }
+
+    servers
+}
+
+/// Does JACK have a shared memory segment?
+fn jack_shm() -> bool
+{
+    std::fs::read_dir(Path::new(DEV_SHM))
+        .map(|dir| {
+            dir.flatten()
+                .any(|entry| entry.file_name().to_string_lossy().starts_with("jack"))
+        })
+        .unwrap_or(false)
+}
+
+/// Get the names of every running process
+fn process_names() -> Vec<String>
+{
+    std::fs::read_dir(PROC)
+        .map(|dir| {
+            dir.flatten()
+                .filter(|entry| {
+                    entry
+                        .file_name()
+                        .to_string_lossy()
+                        .chars()
+                        .all(|c| c.is_ascii_digit())
+                })
+                .filter_map(|entry| {
+                    let mut comm = String::new();
+                    File::open(entry.path().join("comm"))
+                        .and_then(|mut f| f.read_to_string(&mut comm))
+                        .ok()?;
+                    Some(comm.trim().to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
@@ -29,7 +29,7 @@ fn caller_shell() -> Result<String, InfoError>
                 {
                     x.to_string_lossy()
                         .split('/')
-                        .last()
+                        .next_back()
                         .unwrap_or_default()
                         .to_string()
                 }
@@ -0,0 +1,303 @@
+use super::*;
+use std::path::Path;
+
+/// Limits imposed on this process by the cgroup it belongs to
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Default)]
+pub struct CgroupLimits
+{
+    /// The effective memory limit in Bytes
+    pub memory_max: Option<u64>,
+
+    /// Memory charged to the cgroup in Bytes
+    pub memory_current: Option<u64>,
+
+    /// The effective CPU limit in CPUs
+    pub cpu_max: Option<f64>,
+}
+
+/// Detect cgroup v1/v2 membership and read the limits that apply to us.
+/// Returns `None` if we aren't in a (limited) cgroup.
+pub fn cgroup_limits() -> Option<CgroupLimits>
+{
+    let mut self_cgroup = String::new();
+    File::open(PROC_SELF_CGROUP)
+        .and_then(|mut f| f.read_to_string(&mut self_cgroup))
+        .ok()?;
+
+    read_limits(&self_cgroup, Path::new(SYS_CGROUP))
+}
+
+/// Read the limits for the cgroups listed in `self_cgroup` (the contents of
+/// `/proc/self/cgroup`) from the hierarchies mounted under `sys_cgroup`
+fn read_limits(self_cgroup: &str, sys_cgroup: &Path) -> Option<CgroupLimits>
+{
+    let mut limits = CgroupLimits::default();
+
+    for line in self_cgroup.lines()
+    {
+        // Each line is `hierarchy-ID:controller-list:cgroup-path`
+        let mut fields = line.splitn(3, ':');
+        let (Some(id), Some(controllers), Some(path)) =
+            (fields.next(), fields.next(), fields.next())
+        else
+        {
+            continue;
+        };
+        let path = path.trim_start_matches('/');
+
+        if id == "0" && controllers.is_empty()
+        {
+            // cgroup v2, possibly mounted at `unified` on hybrid systems. v1
+            // systems list it too, without mounting it.
+            let Some(root) = [sys_cgroup.to_path_buf(), sys_cgroup.join("unified")]
+                .into_iter()
+                .find(|root| root.join("cgroup.controllers").is_file())
+            else
+            {
+                continue;
+            };
+            let root = root.as_path();
+
+            limits.memory_max = limits
+                .memory_max
+                .or_else(|| walk_min(root, path, |dir| read_u64(dir.join("memory.max"))));
+            limits.memory_current = limits
+                .memory_current
+                .or_else(|| read_u64(root.join(path).join("memory.current")));
+            limits.cpu_max = limits
+                .cpu_max
+                .or_else(|| walk_min(root, path, |dir| cpu_max_v2(&dir.join("cpu.max"))));
+        }
+        else
+        {
+            let root = sys_cgroup.join(controllers);
+            let controllers: Vec<&str> = controllers.split(',').collect();
+
+            if controllers.contains(&"memory")
+            {
+                limits.memory_max = walk_min(&root, path, |dir| {
+                    read_u64(dir.join("memory.limit_in_bytes"))
+                });
+                limits.memory_current = read_u64(root.join(path).join("memory.usage_in_bytes"))
+                    .or_else(|| read_u64(root.join("memory.usage_in_bytes")));
+            }
+
+            if controllers.contains(&"cpu")
+            {
+                limits.cpu_max = walk_min(&root, path, |dir| {
+                    let quota = read_u64(dir.join("cpu.cfs_quota_us"))?;
+                    let period = read_u64(dir.join("cpu.cfs_period_us"))?;
+                    Some(quota as f64 / period as f64)
+                });
+            }
+        }
+    }
+
+    if limits.memory_max.is_none() && limits.cpu_max.is_none()
+    {
+        None
+    }
+    else
+    {
+        Some(limits)
+    }
+}
+
+/// Are we running in a container? Container runtimes leave a marker file or a
+/// `container` environment variable, or name our cgroup after themselves.
+pub fn in_container() -> bool
+{
+    if env::var_os("container").is_some()
+        || Path::new(DOCKERENV).exists()
+        || Path::new(RUN_CONTAINERENV).exists()
+    {
+        return true;
+    }
+
+    match std::fs::read_to_string(PROC_SELF_CGROUP)
+    {
+        Ok(cgroup) =>
+        {
+            cgroup
+                .lines()
+                .filter_map(|line| line.splitn(3, ':').nth(2))
+                .any(is_container_cgroup)
+        }
+        Err(_) => false,
+    }
+}
+
+/// Is the cgroup at `path` one a container runtime made for a container?
+/// The runtimes' own services (e.g. `docker.service`, or the `libpod-conmon-`
+/// and `lxc.monitor.` cgroups of their monitors) run on the host.
+fn is_container_cgroup(path: &str) -> bool
+{
+    const SCOPES: [&str; 4] = ["docker-", "libpod-", "crio-", "cri-containerd-"];
+
+    path.split('/').any(|name| {
+        matches!(name, "docker" | "lxc")
+            || name.starts_with("kubepods")
+            || name.starts_with("lxc.payload.")
+            || (name.ends_with(".scope")
+                && !name.starts_with("libpod-conmon-")
+                && SCOPES.iter().any(|scope| name.starts_with(scope)))
+    })
+}
+
+/// Get the smallest value `read` returns for the cgroup at `path` and each of
+/// its ancestors, since a parent's limit also applies to its children.
+///
+/// When the cgroup namespace hides our path from the mount, only the
+/// mount root is read.
+fn walk_min<T, F>(root: &Path, path: &str, read: F) -> Option<T>
+where
+    T: PartialOrd,
+    F: Fn(&Path) -> Option<T>,
+{
+    let mut dir = root.join(path);
+    if !dir.is_dir()
+    {
+        dir = root.to_path_buf();
+    }
+
+    let mut min: Option<T> = None;
+    loop
+    {
+        match (read(&dir), &min)
+        {
+            (Some(value), Some(current)) if value >= *current => (),
+            (Some(value), _) => min = Some(value),
+            (None, _) => (),
+        }
+
+        if dir == root || !dir.pop()
+        {
+            break;
+        }
+    }
+
+    min
+}
+
+/// Parse a cgroup v2 `cpu.max` file (`$MAX $PERIOD`) into a number of CPUs
+fn cpu_max_v2(path: &Path) -> Option<f64>
+{
+    let contents = std::fs::read_to_string(path).ok()?;
+    let mut fields = contents.split_whitespace();
+    let max: f64 = fields.next()?.parse().ok()?;
+    let period: f64 = fields.next()?.parse().ok()?;
+    Some(max / period)
+}
+
+/// Read a file holding a single integer. `max` and negative values (both
+/// meaning "unlimited") yield `None`.
+fn read_u64(path: impl AsRef<Path>) -> Option<u64>
+{
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// Write `files` (paths relative to the root, and their contents) under a
+    /// new directory standing in for `/sys/fs/cgroup`
+    fn fixture(name: &str, files: &[(&str, &str)]) -> std::path::PathBuf
+    {
+        let root = env::temp_dir().join(format!("rinfo-cgroup-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        for (path, contents) in files
+        {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn recognises_container_cgroups()
+    {
+        for path in [
+            "/docker/3f4e0c1b2a9d",
+            "/system.slice/docker-3f4e0c1b2a9d.scope",
+            "/kubepods/burstable/pod1234/3f4e0c1b2a9d",
+            "/kubepods.slice/kubepods-besteffort.slice/cri-containerd-3f4e.scope",
+            "/machine.slice/libpod-3f4e0c1b2a9d.scope/container",
+            "/lxc/web",
+            "/lxc.payload.web",
+        ]
+        {
+            assert!(is_container_cgroup(path), "{path}");
+        }
+        for path in [
+            "/",
+            "/user.slice/user-1000.slice/session-2.scope",
+            "/system.slice/docker.service",
+            "/system.slice/containerd.service",
+            "/machine.slice/libpod-conmon-3f4e0c1b2a9d.scope",
+            "/lxc.monitor.web",
+        ]
+        {
+            assert!(!is_container_cgroup(path), "{path}");
+        }
+    }
+
+    #[test]
+    fn reads_limits_from_each_cgroup_version()
+    {
+        let v1_cgroup = concat!(
+            "12:memory:/docker/abc\n",
+            "4:cpu,cpuacct:/docker/abc\n",
+            "1:name=systemd:/docker/abc\n",
+            "0::/system.slice/containerd.service\n",
+        );
+        let v1_files = [
+            ("memory/docker/abc/memory.limit_in_bytes", "536870912\n"),
+            ("memory/docker/abc/memory.usage_in_bytes", "1048576\n"),
+            ("cpu,cpuacct/docker/abc/cpu.cfs_quota_us", "150000\n"),
+            ("cpu,cpuacct/docker/abc/cpu.cfs_period_us", "100000\n"),
+        ];
+        let v1_limits = Some(CgroupLimits {
+            memory_max: Some(536870912),
+            memory_current: Some(1048576),
+            cpu_max: Some(1.5),
+        });
+
+        let root = fixture("v1", &v1_files);
+        assert_eq!(read_limits(v1_cgroup, &root), v1_limits);
+
+        // Hybrid systems mount cgroup v2, without controllers, at `unified`
+        let mut hybrid_files = v1_files.to_vec();
+        hybrid_files.push(("unified/cgroup.controllers", ""));
+        let hybrid = fixture("hybrid", &hybrid_files);
+        assert_eq!(read_limits(v1_cgroup, &hybrid), v1_limits);
+
+        let v2 = fixture(
+            "v2",
+            &[
+                ("cgroup.controllers", "cpu memory\n"),
+                ("user.slice/memory.max", "max\n"),
+                ("user.slice/cpu.max", "max 100000\n"),
+                ("user.slice/app/memory.max", "1073741824\n"),
+                ("user.slice/app/memory.current", "4096\n"),
+                ("user.slice/app/cpu.max", "200000 100000\n"),
+            ],
+        );
+        assert_eq!(
+            read_limits("0::/user.slice/app\n", &v2),
+            Some(CgroupLimits {
+                memory_max: Some(1073741824),
+                memory_current: Some(4096),
+                cpu_max: Some(2.0),
+            })
+        );
+        assert_eq!(read_limits("0::/user.slice\n", &v2), None);
+
+        for root in [root, hybrid, v2]
+        {
+            std::fs::remove_dir_all(root).unwrap();
+        }
+    }
+}
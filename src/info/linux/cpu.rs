@@ -33,6 +33,9 @@ pub fn cpu_info() -> Result<Cpu, InfoError>
     let (cores, threads) = cpu_count(Rc::clone(&cpu_info));
     let clock_rate = cpu_clock(Rc::clone(&cpu_info))?;
     let uptime = cpu_uptime(Rc::clone(&uptime_info)) as u128;
+    let limit = cgroup_limits()
+        .and_then(|limits| limits.cpu_max)
+        .filter(|&max| max < threads as f64);
 
     Ok(Cpu {
         name,
@@ -40,6 +43,42 @@ pub fn cpu_info() -> Result<Cpu, InfoError>
         cores,
         threads,
         clock_rate,
+        limit,
+        temperature: None,
+    })
+}
+
+/// The CPU package temperature in degrees Celsius, from the first hwmon
+/// sensor of a known CPU temperature driver
+pub fn cpu_temperature() -> Option<f64>
+{
+    const CPU_SENSORS: [&str; 5] = [
+        "coretemp",
+        "k10temp",
+        "zenpower",
+        "cpu_thermal",
+        "soc_thermal",
+    ];
+
+    let mut sensors: Vec<PathBuf> = std::fs::read_dir(SYS_CLASS_HWMON)
+        .ok()?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .collect();
+    sensors.sort();
+
+    sensors.iter().find_map(|sensor| {
+        let name = std::fs::read_to_string(sensor.join("name")).ok()?;
+        if !CPU_SENSORS.contains(&name.trim())
+        {
+            return None;
+        }
+
+        let millidegrees: f64 = std::fs::read_to_string(sensor.join("temp1_input"))
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        Some(millidegrees / 1000.0)
     })
 }
 
@@ -0,0 +1,388 @@
+use super::*;
+use std::path::Path;
+
+/// Where distributions install the PCI ID database
+const PCI_IDS: [&str; 3] = [
+    "/usr/share/hwdata/pci.ids",
+    "/usr/share/misc/pci.ids",
+    "/usr/share/pci.ids",
+];
+
+/// Where distributions install the USB ID database
+const USB_IDS: [&str; 3] = [
+    "/usr/share/hwdata/usb.ids",
+    "/usr/share/misc/usb.ids",
+    "/var/lib/usbutils/usb.ids",
+];
+
+/// Vendor, product, and class names from a `pci.ids`/`usb.ids` file
+#[derive(Debug, Default)]
+struct IdDatabase
+{
+    vendors: HashMap<u16, String>,
+    products: HashMap<(u16, u16), String>,
+    classes: HashMap<u8, String>,
+    subclasses: HashMap<(u8, u8), String>,
+}
+
+impl IdDatabase
+{
+    /// Load the first ID database that exists. A missing database only means
+    /// we can't resolve names.
+    fn load(paths: &[&str]) -> Self
+    {
+        paths
+            .iter()
+            .find_map(|path| std::fs::read(path).ok())
+            .map(|contents| Self::parse(&String::from_utf8_lossy(&contents)))
+            .unwrap_or_default()
+    }
+
+    fn parse(contents: &str) -> Self
+    {
+        enum Section
+        {
+            Vendor(u16),
+            Class(u8),
+            Other,
+        }
+
+        let mut db = Self::default();
+        let mut section = Section::Other;
+
+        for line in contents.lines()
+        {
+            if line.starts_with('#') || line.trim().is_empty()
+            {
+                continue;
+            }
+
+            let depth = line.chars().take_while(|&c| c == '\t').count();
+            let Some((id, name)) = line.trim_start_matches('\t').split_once("  ")
+            else
+            {
+                continue;
+            };
+            let name = name.trim().to_string();
+
+            match (depth, &section)
+            {
+                (0, _) =>
+                {
+                    section = if let Some(class) = id.strip_prefix("C ")
+                    {
+                        match u8::from_str_radix(class, 16)
+                        {
+                            Ok(class) =>
+                            {
+                                db.classes.insert(class, name);
+                                Section::Class(class)
+                            }
+                            Err(_) => Section::Other,
+                        }
+                    }
+                    else
+                    {
+                        // Sections other than vendors and classes (e.g. `AT`,
+                        // `HID` in `usb.ids`) aren't keyed by a bare hex ID
+                        match u16::from_str_radix(id, 16)
+                        {
+                            Ok(vendor) =>
+                            {
+                                db.vendors.insert(vendor, name);
+                                Section::Vendor(vendor)
+                            }
+                            Err(_) => Section::Other,
+                        }
+                    };
+                }
+                (1, Section::Vendor(vendor)) =>
+                {
+                    if let Ok(product) = u16::from_str_radix(id, 16)
+                    {
+                        db.products.insert((*vendor, product), name);
+                    }
+                }
+                (1, Section::Class(class)) =>
+                {
+                    if let Ok(subclass) = u8::from_str_radix(id, 16)
+                    {
+                        db.subclasses.insert((*class, subclass), name);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        db
+    }
+
+    fn vendor(&self, vendor: u16) -> Option<String> { self.vendors.get(&vendor).cloned() }
+
+    fn product(&self, vendor: u16, product: u16) -> Option<String>
+    {
+        self.products.get(&(vendor, product)).cloned()
+    }
+
+    /// Name a class and, if it's listed, its subclass
+    fn class(&self, class: u8, subclass: u8) -> (String, Option<String>)
+    {
+        (
+            self.classes
+                .get(&class)
+                .cloned()
+                .unwrap_or_else(|| format!("Class {class:02x}")),
+            self.subclasses.get(&(class, subclass)).cloned(),
+        )
+    }
+}
+
+pub fn devices_info() -> Result<Devices, InfoError>
+{
+    Ok(Devices {
+        pci: pci_devices(),
+        usb: usb_devices(),
+    })
+}
+
+/// Read a sysfs attribute, trimmed
+fn read_attr(dir: &Path, attr: &str) -> Option<String>
+{
+    let mut contents = String::new();
+    File::open(dir.join(attr))
+        .and_then(|mut f| f.read_to_string(&mut contents))
+        .ok()?;
+    Some(contents.trim().to_string())
+}
+
+/// Read a hexadecimal sysfs attribute (e.g. `0x8086` or `046d`)
+fn read_hex(dir: &Path, attr: &str) -> Option<u32>
+{
+    let value = read_attr(dir, attr)?;
+    u32::from_str_radix(value.trim_start_matches("0x"), 16).ok()
+}
+
+/// Get the name of the driver bound to a device
+fn driver(dir: &Path) -> Option<String>
+{
+    let driver = std::fs::read_link(dir.join("driver")).ok()?;
+    Some(driver.file_name()?.to_string_lossy().to_string())
+}
+
+/// List the entries of a sysfs bus directory in order
+fn bus_entries(bus: &str) -> Vec<PathBuf>
+{
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(bus)
+        .map(|dir| dir.flatten().map(|entry| entry.path()).collect())
+        .unwrap_or_default();
+    entries.sort();
+    entries
+}
+
+fn pci_devices() -> Vec<PciDevice>
+{
+    let db = IdDatabase::load(&PCI_IDS);
+
+    bus_entries(SYS_BUS_PCI_DEVICES)
+        .iter()
+        .filter_map(|dir| {
+            let vendor_id = read_hex(dir, "vendor")? as u16;
+            let device_id = read_hex(dir, "device")? as u16;
+            // `class` is `0xCCSSPP`: class, subclass, programming interface
+            let class = read_hex(dir, "class").unwrap_or_default();
+            let (class, subclass) = db.class((class >> 16) as u8, (class >> 8) as u8);
+
+            Some(PciDevice {
+                slot: dir.file_name()?.to_string_lossy().to_string(),
+                vendor: db
+                    .vendor(vendor_id)
+                    .unwrap_or_else(|| format!("{vendor_id:04x}")),
+                device: db
+                    .product(vendor_id, device_id)
+                    .unwrap_or_else(|| format!("{device_id:04x}")),
+                vendor_id,
+                device_id,
+                class,
+                subclass,
+                driver: driver(dir),
+            })
+        })
+        .collect()
+}
+
+fn usb_devices() -> Vec<UsbDevice>
+{
+    let db = IdDatabase::load(&USB_IDS);
+
+    bus_entries(SYS_BUS_USB_DEVICES)
+        .iter()
+        // Interfaces (e.g. `1-1:1.0`) are listed alongside devices
+        .filter(|dir| {
+            !dir.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .contains(':')
+        })
+        .filter_map(|dir| {
+            let vendor_id = read_hex(dir, "idVendor")? as u16;
+            let product_id = read_hex(dir, "idProduct")? as u16;
+            let interfaces = usb_interfaces(dir);
+
+            // A device class of 0 means each interface declares its own
+            let (mut class, mut subclass) = (
+                read_hex(dir, "bDeviceClass").unwrap_or_default() as u8,
+                read_hex(dir, "bDeviceSubClass").unwrap_or_default() as u8,
+            );
+            if class == 0
+            {
+                if let Some(interface) = interfaces.first()
+                {
+                    class = read_hex(interface, "bInterfaceClass").unwrap_or_default() as u8;
+                    subclass = read_hex(interface, "bInterfaceSubClass").unwrap_or_default() as u8;
+                }
+            }
+
+            let (class, subclass) = db.class(class, subclass);
+
+            let mut drivers: Vec<String> = interfaces.iter().filter_map(|i| driver(i)).collect();
+            drivers.sort();
+            drivers.dedup();
+
+            Some(UsbDevice {
+                bus: read_attr(dir, "busnum")?.parse().ok()?,
+                address: read_attr(dir, "devnum")?.parse().ok()?,
+                vendor: db
+                    .vendor(vendor_id)
+                    .or_else(|| read_attr(dir, "manufacturer"))
+                    .unwrap_or_else(|| format!("{vendor_id:04x}")),
+                product: db
+                    .product(vendor_id, product_id)
+                    .or_else(|| read_attr(dir, "product"))
+                    .unwrap_or_else(|| format!("{product_id:04x}")),
+                vendor_id,
+                product_id,
+                class,
+                subclass,
+                drivers,
+                speed: read_attr(dir, "speed").and_then(|speed| speed.parse().ok()),
+            })
+        })
+        .collect()
+}
+
+/// Get the interface directories of a USB device, e.g. `1-1:1.0`
+fn usb_interfaces(dir: &Path) -> Vec<PathBuf>
+{
+    let mut interfaces: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .contains(':')
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    interfaces.sort();
+    interfaces
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn parses_id_databases()
+    {
+        let pci = IdDatabase::parse(concat!(
+            "# List of PCI ID's\n",
+            "\n",
+            "8086  Intel Corporation\n",
+            "\t1237  440FX - 82441FX PMC [Natoma]\n",
+            "\t\t1af4 1100  Qemu virtual machine\n",
+            "\t7000  82371SB PIIX3 ISA [Natoma/Triton II]\n",
+            "10de  NVIDIA Corporation\n",
+            "\t2684  AD102 [GeForce RTX 4090]\n",
+            "\n",
+            "# List of known device classes, subclasses and programming interfaces\n",
+            "C 03  Display controller\n",
+            "\t00  VGA compatible controller\n",
+            "\t\t00  VGA controller\n",
+            "\t\t01  8514 controller\n",
+            "\t80  Display controller\n",
+            "C 0c  Serial bus controller\n",
+            "\t03  USB controller\n",
+        ));
+        assert_eq!(pci.vendor(0x8086).as_deref(), Some("Intel Corporation"));
+        assert_eq!(
+            pci.product(0x8086, 0x7000).as_deref(),
+            Some("82371SB PIIX3 ISA [Natoma/Triton II]")
+        );
+        assert_eq!(
+            pci.product(0x10DE, 0x2684).as_deref(),
+            Some("AD102 [GeForce RTX 4090]")
+        );
+        // Subsystems and programming interfaces (two tabs) aren't products or
+        // subclasses
+        assert_eq!(pci.product(0x8086, 0x1AF4), None);
+        assert_eq!(pci.product(0x10DE, 0x1237), None);
+        assert_eq!(
+            pci.class(0x03, 0x00),
+            (
+                "Display controller".to_string(),
+                Some("VGA compatible controller".to_string())
+            )
+        );
+        assert_eq!(
+            pci.class(0x03, 0x02),
+            ("Display controller".to_string(), None)
+        );
+        assert_eq!(
+            pci.class(0x0C, 0x03),
+            (
+                "Serial bus controller".to_string(),
+                Some("USB controller".to_string())
+            )
+        );
+        assert_eq!(pci.class(0x0D, 0x11), ("Class 0d".to_string(), None));
+
+        let usb = IdDatabase::parse(concat!(
+            "046d  Logitech, Inc.\n",
+            "\tc52b  Unifying Receiver\n",
+            "\tc534  Unifying Receiver\n",
+            "1d6b  Linux Foundation\n",
+            "\t0002  2.0 root hub\n",
+            "C 03  Human Interface Device\n",
+            "\t01  Boot Interface Subclass\n",
+            "\t\t01  Keyboard\n",
+            "\t\t02  Mouse\n",
+            "C 09  Hub\n",
+            "\t00  Unused\n",
+            "AT 0401  Audio Terminal, Line Connector\n",
+            "HID 21  HID\n",
+            "HUT 01  Generic Desktop Controls\n",
+            "\t002  Mouse\n",
+            "L 0409  English\n",
+            "\t01  US\n",
+        ));
+        assert_eq!(usb.vendor(0x046D).as_deref(), Some("Logitech, Inc."));
+        assert_eq!(
+            usb.product(0x046D, 0xC534).as_deref(),
+            Some("Unifying Receiver")
+        );
+        assert_eq!(usb.product(0x1D6B, 0x0002).as_deref(), Some("2.0 root hub"));
+        assert_eq!(usb.class(0x03, 0x01).0, "Human Interface Device");
+        assert_eq!(usb.class(0x09, 0x00).0, "Hub");
+        assert_eq!(usb.class(0x09, 0x00).1.as_deref(), Some("Unused"));
+        // Other sections' entries aren't mistaken for vendors or classes
+        assert_eq!(usb.vendors.len(), 2);
+        assert_eq!(usb.products.len(), 3);
+        assert_eq!(usb.classes.len(), 2);
+        assert_eq!(usb.subclasses.len(), 2);
+    }
+}
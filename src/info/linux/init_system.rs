@@ -0,0 +1,126 @@
+use super::*;
+use std::path::Path;
+
+pub fn init_info() -> Result<Init, InfoError>
+{
+    let mut comm = String::new();
+
+    if File::open(PROC_INIT_COMM)
+        .and_then(|mut f| f.read_to_string(&mut comm))
+        .is_err()
+    {
+        return Err(InfoError::FileRead {
+            path: PROC_INIT_COMM.to_string(),
+        });
+    }
+
+    // `comm` can be misleading (e.g. `init` is a symlink to systemd on most
+    // distributions), so prefer the executable when we're allowed to read it
+    let exe = std::fs::read_link(PROC_INIT_EXE)
+        .ok()
+        .and_then(|exe| Some(exe.file_name()?.to_string_lossy().to_string()));
+
+    let kind = init_kind(exe.as_deref().unwrap_or_else(|| comm.trim()));
+
+    let (state, failed_units) = match kind
+    {
+        InitKind::Systemd =>
+        {
+            (
+                systemd_state(Path::new(RUN_SYSTEMD_SYSTEM), Path::new(RUN_NOLOGIN)),
+                None,
+            )
+        }
+        InitKind::OpenRc => (None, openrc_failed_services(Path::new(RUN_OPENRC_FAILED))),
+        _ => (None, None),
+    };
+
+    Ok(Init {
+        kind,
+        state,
+        failed_units,
+    })
+}
+
+/// Identify the init system from the name of PID 1
+fn init_kind(name: &str) -> InitKind
+{
+    match name
+    {
+        "systemd" => InitKind::Systemd,
+        "openrc-init" => InitKind::OpenRc,
+        "runit" | "runit-init" => InitKind::Runit,
+        "s6-svscan" => InitKind::S6,
+        "dinit" => InitKind::Dinit,
+        "tini" | "docker-init" => InitKind::Tini,
+        "dumb-init" => InitKind::DumbInit,
+        // OpenRC can also run on top of sysvinit
+        "init" if Path::new(RUN_OPENRC).is_dir() => InitKind::OpenRc,
+        "init" if Path::new(RUN_SYSTEMD_SYSTEM).is_dir() => InitKind::Systemd,
+        "init" => InitKind::SysVinit,
+        name => InitKind::Other(name.to_string()),
+    }
+}
+
+/// systemd's state, from the files it leaves in `/run` (its `system`
+/// directory, and `nologin`, which is there until the system has started up).
+/// systemd only tells `running` and `degraded` apart, and counts failed units,
+/// in memory, which it reports over D-Bus, so neither can be read here.
+fn systemd_state(system: &Path, nologin: &Path) -> Option<String>
+{
+    if !system.is_dir()
+    {
+        return None;
+    }
+    let state = if nologin.exists()
+    {
+        "starting"
+    }
+    else
+    {
+        "running"
+    };
+    Some(state.to_string())
+}
+
+/// Count the services OpenRC failed to start, which it marks in its `failed`
+/// directory
+fn openrc_failed_services(failed: &Path) -> Option<usize>
+{
+    let failed = std::fs::read_dir(failed).ok()?;
+    Some(failed.filter_map(Result::ok).count())
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn reads_service_manager_state()
+    {
+        let run = env::temp_dir().join(format!("rinfo-init-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&run);
+        for dir in ["systemd/system", "openrc/failed"]
+        {
+            std::fs::create_dir_all(run.join(dir)).unwrap();
+        }
+        for service in ["sshd", "nfs"]
+        {
+            std::fs::write(run.join("openrc/failed").join(service), "").unwrap();
+        }
+
+        let (system, nologin) = (run.join("systemd/system"), run.join("nologin"));
+        assert_eq!(systemd_state(&system, &nologin).as_deref(), Some("running"));
+        std::fs::write(&nologin, "").unwrap();
+        assert_eq!(
+            systemd_state(&system, &nologin).as_deref(),
+            Some("starting")
+        );
+        assert_eq!(systemd_state(&run.join("systemd/none"), &nologin), None);
+
+        assert_eq!(openrc_failed_services(&run.join("openrc/failed")), Some(2));
+        assert_eq!(openrc_failed_services(&run.join("openrc/none")), None);
+        std::fs::remove_dir_all(&run).unwrap();
+    }
+}
@@ -0,0 +1,173 @@
+use super::*;
+use std::time::Duration;
+
+pub fn load_info(interval: Option<Duration>) -> Result<Load, InfoError>
+{
+    let mut loadavg = String::new();
+
+    if File::open(PROC_LOADAVG)
+        .and_then(|mut f| f.read_to_string(&mut loadavg))
+        .is_err()
+    {
+        return Err(InfoError::FileRead {
+            path: PROC_LOADAVG.to_string(),
+        });
+    }
+
+    let (average, running, total) = parse_loadavg(&loadavg)?;
+
+    let cpu_usage = match interval
+    {
+        Some(interval) =>
+        {
+            let first = cpu_times()?;
+            std::thread::sleep(interval);
+            Some(cpu_usage(first, cpu_times()?))
+        }
+        None => None,
+    };
+
+    Ok(Load {
+        average,
+        running: Some(running),
+        total: Some(total),
+        cpu_usage,
+        pressure: pressure_info(),
+    })
+}
+
+/// Parse the load averages and the `running/total` process counts from
+/// `/proc/loadavg`
+fn parse_loadavg(loadavg: &str) -> Result<([f64; 3], usize, usize), InfoError>
+{
+    let parse_error = |reason: String| {
+        InfoError::FileParseError {
+            path: PROC_LOADAVG.to_string(),
+            reason,
+        }
+    };
+
+    let mut fields = loadavg.split_whitespace();
+    let mut average = [0.0; 3];
+    for avg in &mut average
+    {
+        *avg = fields
+            .next()
+            .ok_or_else(|| parse_error("missing load average".to_string()))?
+            .parse()
+            .map_err(|e: std::num::ParseFloatError| parse_error(e.to_string()))?;
+    }
+
+    let (running, total) = fields
+        .next()
+        .and_then(|procs| procs.split_once('/'))
+        .ok_or_else(|| parse_error("missing process counts".to_string()))?;
+    let running = running
+        .parse()
+        .map_err(|e: std::num::ParseIntError| parse_error(e.to_string()))?;
+    let total = total
+        .parse()
+        .map_err(|e: std::num::ParseIntError| parse_error(e.to_string()))?;
+
+    Ok((average, running, total))
+}
+
+/// Get the aggregate `(idle, total)` CPU time from `/proc/stat`
+fn cpu_times() -> Result<(u64, u64), InfoError>
+{
+    let mut stat = String::new();
+
+    if File::open(PROC_STAT)
+        .and_then(|mut f| f.read_to_string(&mut stat))
+        .is_err()
+    {
+        return Err(InfoError::FileRead {
+            path: PROC_STAT.to_string(),
+        });
+    }
+
+    let cpu_line = stat
+        .lines()
+        .find(|line| line.starts_with("cpu "))
+        .ok_or_else(|| {
+            InfoError::General("Couldn't find line that starts with 'cpu '".to_string())
+        })?;
+
+    // user nice system idle iowait irq softirq steal (guest time is already
+    // accounted for in user and nice)
+    let times: Vec<u64> = cpu_line
+        .split_whitespace()
+        .skip(1)
+        .take(8)
+        .filter_map(|time| time.parse().ok())
+        .collect();
+
+    if times.len() < 4
+    {
+        return Err(InfoError::FileParseError {
+            path: PROC_STAT.to_string(),
+            reason: "too few fields on the 'cpu' line".to_string(),
+        });
+    }
+
+    let idle = times[3] + times.get(4).unwrap_or(&0);
+    Ok((idle, times.iter().sum()))
+}
+
+/// The percentage of time the CPU wasn't idle between two samples
+fn cpu_usage((idle_a, total_a): (u64, u64), (idle_b, total_b): (u64, u64)) -> f64
+{
+    let total = total_b.saturating_sub(total_a);
+    if total == 0
+    {
+        return 0.0;
+    }
+
+    let idle = idle_b.saturating_sub(idle_a);
+    (total - idle.min(total)) as f64 / total as f64 * 100.0
+}
+
+/// Read the pressure stall information, if the kernel exposes it
+fn pressure_info() -> Option<Pressure>
+{
+    let read = |resource: &str| {
+        let mut pressure = String::new();
+        File::open(format!("{PROC_PRESSURE}/{resource}"))
+            .and_then(|mut f| f.read_to_string(&mut pressure))
+            .ok()?;
+        parse_pressure(&pressure)
+    };
+
+    let pressure = Pressure {
+        cpu: read("cpu"),
+        memory: read("memory"),
+        io: read("io"),
+    };
+
+    if pressure.cpu.is_none() && pressure.memory.is_none() && pressure.io.is_none()
+    {
+        None
+    }
+    else
+    {
+        Some(pressure)
+    }
+}
+
+/// Parse the `some` line of a PSI file, e.g.
+/// `some avg10=0.00 avg60=0.00 avg300=0.00 total=0`
+fn parse_pressure(pressure: &str) -> Option<PressureStall>
+{
+    let line = pressure.lines().find(|line| line.starts_with("some"))?;
+    let field = |name: &str| {
+        line.split_whitespace()
+            .find_map(|field| field.strip_prefix(name)?.strip_prefix('='))
+            .and_then(|value| value.parse().ok())
+    };
+
+    Some(PressureStall {
+        avg10: field("avg10")?,
+        avg60: field("avg60")?,
+        avg300: field("avg300")?,
+    })
+}
@@ -0,0 +1,135 @@
+use super::*;
+
+/// The locale categories that can be set independently of `LANG`
+const LC_CATEGORIES: [&str; 12] = [
+    "LC_CTYPE",
+    "LC_NUMERIC",
+    "LC_TIME",
+    "LC_COLLATE",
+    "LC_MONETARY",
+    "LC_MESSAGES",
+    "LC_PAPER",
+    "LC_NAME",
+    "LC_ADDRESS",
+    "LC_TELEPHONE",
+    "LC_MEASUREMENT",
+    "LC_IDENTIFICATION",
+];
+
+pub fn locale_info() -> Result<Locale, InfoError>
+{
+    // Fall back to the system-wide settings when the environment doesn't
+    // have them (e.g. under cron or a bare `env -i`)
+    let locale_conf = read_key_values(ETC_LOCALE_CONF);
+    let lookup = |key: &str| {
+        env::var(key)
+            .ok()
+            .filter(|value| !value.is_empty())
+            .or_else(|| locale_conf.get(key).cloned())
+    };
+
+    let lang = lookup("LANG").unwrap_or_else(|| "C".to_string());
+    let all = lookup("LC_ALL");
+
+    // Resolve each category the way `setlocale` does:
+    // `LC_ALL` > `LC_*` > `LANG`
+    let categories = LC_CATEGORIES
+        .iter()
+        .filter_map(|category| {
+            let value = all.clone().or_else(|| lookup(category))?;
+            (value != lang).then(|| (category.to_string(), value))
+        })
+        .collect();
+
+    let vconsole = read_key_values(ETC_VCONSOLE_CONF);
+
+    Ok(Locale {
+        lang,
+        categories,
+        timezone: timezone(),
+        utc_offset: chrono::Local::now().format("%:z").to_string(),
+        console_keymap: vconsole.get("KEYMAP").cloned(),
+        x11_layout: x11_layout(),
+    })
+}
+
+/// Get the timezone from `TZ`, the `/etc/localtime` symlink or
+/// `/etc/timezone`, in that order
+fn timezone() -> String
+{
+    if let Some(tz) = env::var("TZ").ok().filter(|tz| !tz.is_empty())
+    {
+        return tz.trim_start_matches(':').to_string();
+    }
+
+    if let Ok(target) = std::fs::read_link(ETC_LOCALTIME)
+    {
+        let target = target.to_string_lossy();
+        if let Some((_, zone)) = target.split_once("zoneinfo/")
+        {
+            return zone.to_string();
+        }
+    }
+
+    let mut timezone = String::new();
+    match File::open(ETC_TIMEZONE).and_then(|mut f| f.read_to_string(&mut timezone))
+    {
+        Ok(_) if !timezone.trim().is_empty() => timezone.trim().to_string(),
+        _ => "UTC".to_string(),
+    }
+}
+
+/// Get the X11 keyboard layout (and variant) from `00-keyboard.conf`, e.g.
+/// `Option "XkbLayout" "us,de"`
+fn x11_layout() -> Option<String>
+{
+    let mut keyboard_conf = String::new();
+    File::open(ETC_X11_KEYBOARD_CONF)
+        .and_then(|mut f| f.read_to_string(&mut keyboard_conf))
+        .ok()?;
+
+    let option = |name: &str| {
+        keyboard_conf.lines().find_map(|line| {
+            let rest = line.trim().strip_prefix("Option")?.trim();
+            let value = rest.strip_prefix(&format!("\"{name}\""))?;
+            Some(value.trim().trim_matches('"').to_string())
+        })
+    };
+
+    let layout = option("XkbLayout")?;
+    Some(
+        match option("XkbVariant").filter(|variant| !variant.is_empty())
+        {
+            Some(variant) => format!("{layout} ({variant})"),
+            None => layout,
+        },
+    )
+}
+
+/// Read a shell-style `KEY=value` file, ignoring comments
+fn read_key_values(path: &str) -> HashMap<String, String>
+{
+    let mut contents = String::new();
+    if File::open(path)
+        .and_then(|mut f| f.read_to_string(&mut contents))
+        .is_err()
+    {
+        return HashMap::new();
+    }
+
+    contents
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            (
+                key.trim().to_string(),
+                value
+                    .trim()
+                    .trim_matches('"')
+                    .trim_matches('\'')
+                    .to_string(),
+            )
+        })
+        .collect()
+}
@@ -28,7 +28,7 @@ pub fn memory_info() -> Result<Memory, InfoError>
 
     let total = match meminfo_total
         .split(':')
-        .last()
+        .next_back()
         .unwrap_or_default()
         .trim()
         .split(' ')
@@ -59,7 +59,7 @@ pub fn memory_info() -> Result<Memory, InfoError>
 
     let available = match meminfo_available
         .split(':')
-        .last()
+        .next_back()
         .unwrap_or_default()
         .trim()
         .split(' ')
@@ -79,10 +79,22 @@ pub fn memory_info() -> Result<Memory, InfoError>
     } * 1024.0;
 
     let used = total - available;
+    let total = total as u64;
+
+    // Inside a container the host's totals are misleading, so report the
+    // cgroup's limit too
+    let limit = cgroup_limits().and_then(|limits| {
+        let limit = limits.memory_max.filter(|&max| max < total)?;
+        Some(MemoryLimit {
+            total: limit,
+            used: limits.memory_current.unwrap_or_default(),
+        })
+    });
 
     Ok(Memory {
-        total: total as u64,
+        total,
         available: available as u64,
         used: used as u64,
+        limit,
     })
 }
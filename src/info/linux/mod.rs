@@ -2,15 +2,20 @@ use super::common;
 use crate::info::*;
 use lazy_static::lazy_static;
 use libc::{
-    close, connect, getsockname, in_addr, sockaddr, sockaddr_in, socket, AF_INET, SOCK_DGRAM,
+    close, connect, freeifaddrs, getifaddrs, getsockname, ifaddrs, in_addr, sockaddr, sockaddr_in,
+    socket, AF_INET, SOCK_DGRAM,
+};
+use std::{
+    collections::HashMap, env, ffi::CStr, fs::File, io::Read, mem::size_of, path::PathBuf,
+    ptr::null_mut, sync::Mutex,
 };
-use std::{env, fs::File, io::Read, mem::size_of, path::PathBuf, sync::Mutex};
 
 lazy_static! {
     /// Has the initialization function ran?
     pub static ref INITIALIZED: Mutex<bool> = Mutex::new(false);
 }
 
+const PROC: &str = "/proc";
 const PROC_CPUINFO: &str = "/proc/cpuinfo";
 const PROC_UPTIME: &str = "/proc/uptime";
 const PROC_MEMINFO: &str = "/proc/meminfo";
@@ -19,6 +24,47 @@ const SYS_BOARD_VENDOR: &str = "/sys/devices/virtual/dmi/id/board_vendor";
 const SYS_BOARD_NAME: &str = "/sys/devices/virtual/dmi/id/board_name";
 const ETC_OS_RELEASE: &str = "/etc/os-release";
 const ETC_LSB_RELEASE: &str = "/etc/lsb-release";
+const USR_LIB_OS_RELEASE: &str = "/usr/lib/os-release";
+const PROC_LOADAVG: &str = "/proc/loadavg";
+const PROC_STAT: &str = "/proc/stat";
+const PROC_PRESSURE: &str = "/proc/pressure";
+const PROC_SELF_CGROUP: &str = "/proc/self/cgroup";
+const SYS_CGROUP: &str = "/sys/fs/cgroup";
+const DOCKERENV: &str = "/.dockerenv";
+const RUN_CONTAINERENV: &str = "/run/.containerenv";
+const ETC_LOCALE_CONF: &str = "/etc/locale.conf";
+const ETC_LOCALTIME: &str = "/etc/localtime";
+const ETC_TIMEZONE: &str = "/etc/timezone";
+const ETC_VCONSOLE_CONF: &str = "/etc/vconsole.conf";
+const ETC_X11_KEYBOARD_CONF: &str = "/etc/X11/xorg.conf.d/00-keyboard.conf";
+const VAR_RUN_UTMP: &str = "/var/run/utmp";
+const PROC_INIT_COMM: &str = "/proc/1/comm";
+const PROC_INIT_EXE: &str = "/proc/1/exe";
+const RUN_SYSTEMD_SYSTEM: &str = "/run/systemd/system";
+const RUN_OPENRC: &str = "/run/openrc";
+const RUN_OPENRC_FAILED: &str = "/run/openrc/failed";
+const RUN_NOLOGIN: &str = "/run/nologin";
+const SYS_SELINUX_ENFORCE: &str = "/sys/fs/selinux/enforce";
+const SYS_APPARMOR: &str = "/sys/kernel/security/apparmor";
+const SYS_APPARMOR_ENABLED: &str = "/sys/module/apparmor/parameters/enabled";
+const SYS_LOCKDOWN: &str = "/sys/kernel/security/lockdown";
+const SYS_FIRMWARE: &str = "/sys/firmware";
+const SYS_EFIVAR_SECURE_BOOT: &str =
+    "/sys/firmware/efi/efivars/SecureBoot-8be4df61-93ca-11d2-aa0d-00e098032b8c";
+const SYS_CPU_VULNERABILITIES: &str = "/sys/devices/system/cpu/vulnerabilities";
+const SYS_BUS_PCI_DEVICES: &str = "/sys/bus/pci/devices";
+const SYS_BUS_USB_DEVICES: &str = "/sys/bus/usb/devices";
+const PROC_ASOUND_CARDS: &str = "/proc/asound/cards";
+const SYS_CLASS_SOUND: &str = "/sys/class/sound";
+const DEV_SHM: &str = "/dev/shm";
+const PROC_NET_WIRELESS: &str = "/proc/net/wireless";
+const SYS_CLASS_BLUETOOTH: &str = "/sys/class/bluetooth";
+const PROC_NET_ROUTE: &str = "/proc/net/route";
+const PROC_NET_IPV6_ROUTE: &str = "/proc/net/ipv6_route";
+const ETC_RESOLV_CONF: &str = "/etc/resolv.conf";
+const RUN_RESOLVED_RESOLV_CONF: &str = "/run/systemd/resolve/resolv.conf";
+const PROC_NET_DEV: &str = "/proc/net/dev";
+const SYS_CLASS_HWMON: &str = "/sys/class/hwmon";
 
 /// Get cpu information on linux platforms using procfs
 mod cpu;
@@ -36,6 +82,50 @@ pub use caller::*;
 mod operating_system;
 pub use operating_system::*;
 
+/// Get the resource limits of the cgroup we're running in
+mod cgroup;
+pub use cgroup::*;
+
+/// Get the system load and CPU utilisation
+mod load;
+pub use load::*;
+
+/// Get the locale, timezone, and keyboard layout
+mod locale;
+pub use locale::*;
+
+/// Get the logged-in users from utmp
+mod users;
+pub use users::*;
+
+/// Get the init system and service manager status
+mod init_system;
+pub use init_system::*;
+
+/// Get the security posture of the system
+mod security;
+pub use security::*;
+
+/// Get the PCI and USB device inventory
+mod devices;
+pub use devices::*;
+
+/// Get the sound cards and sound servers
+mod audio;
+pub use audio::*;
+
+/// Get Wi-Fi and Bluetooth information
+mod wireless;
+pub use wireless::*;
+
+/// Get the default gateways and DNS configuration
+mod route;
+pub use route::*;
+
+/// Sample network throughput
+mod throughput;
+pub use throughput::*;
+
 /// Perform any initalization and pre-checks required
 pub fn init() -> Result<(), InfoError>
 {
@@ -48,6 +138,7 @@ pub fn init() -> Result<(), InfoError>
     );
     let lsb_release_info = PathBuf::from(ETC_LSB_RELEASE);
     let os_release_info = PathBuf::from(ETC_OS_RELEASE);
+    let usr_lib_os_release_info = PathBuf::from(USR_LIB_OS_RELEASE);
 
     // Ensure the files that we need exist
     if !cpu_info.is_file()
@@ -79,7 +170,9 @@ pub fn init() -> Result<(), InfoError>
         });
     }
 
-    if !lsb_release_info.is_file() && !os_release_info.is_file()
+    if !lsb_release_info.is_file()
+        && !os_release_info.is_file()
+        && !usr_lib_os_release_info.is_file()
     {
         return Err(InfoError::MissingFile {
             path: lsb_release_info,
@@ -179,6 +272,69 @@ pub fn motherboard_info() -> Result<BaseBoard, InfoError>
 
 
 pub fn net_info() -> Result<Net, InfoError>
+{
+    // The internet-facing address is the one on the interface the default
+    // route goes through. Without the routing table, ask the kernel which
+    // address it would reach the internet from.
+    let local_ip = match default_routes()
+    {
+        Ok(routes) => interface_ip(routes.into_iter().next().map(|route| route.interface))?,
+        Err(_) => Some(connected_ip()?),
+    };
+
+    Ok(Net {
+        local_ip: local_ip.unwrap_or_else(|| "none".to_string()),
+        wireless: wireless_info()?,
+        bluetooth: bluetooth_info(),
+        throughput: Vec::new(),
+    })
+}
+
+/// Get the IPv4 address of `interface`, or of the first non-loopback
+/// interface when there's no default route
+fn interface_ip(interface: Option<String>) -> Result<Option<String>, InfoError>
+{
+    let mut addresses: *mut ifaddrs = null_mut();
+    if unsafe { getifaddrs(&mut addresses) } == -1
+    {
+        return Err(InfoError::General("getifaddrs failed".to_string()));
+    }
+
+    let mut local_ip = None;
+    let mut address = addresses;
+    while let Some(ifaddr) = unsafe { address.as_ref() }
+    {
+        address = ifaddr.ifa_next;
+
+        if ifaddr.ifa_addr.is_null()
+            || i32::from(unsafe { (*ifaddr.ifa_addr).sa_family }) != AF_INET
+        {
+            continue;
+        }
+
+        let name = unsafe { CStr::from_ptr(ifaddr.ifa_name) }.to_string_lossy();
+        let is_default = interface.as_deref() == Some(&*name);
+        // Without a default route, settle for the first non-loopback address
+        if !is_default && (interface.is_some() || local_ip.is_some() || name == "lo")
+        {
+            continue;
+        }
+
+        let addr = unsafe { &*(ifaddr.ifa_addr as *const sockaddr_in) };
+        local_ip = Some(common::int_to_ipv4(addr.sin_addr.s_addr));
+        if is_default
+        {
+            break;
+        }
+    }
+    unsafe { freeifaddrs(addresses) };
+
+    Ok(local_ip)
+}
+
+/// Get the address a UDP socket connected to a public server is bound to.
+/// Nothing is sent.
+fn connected_ip() -> Result<String, InfoError>
 {
     const IP: &str = "1.1.1.1";
     const PORT: u16 = 53;
@@ -198,7 +354,14 @@ pub fn net_info() -> Result<Net, InfoError>
         sin_zero: [0; 8],
     };
 
-    if unsafe {
+    let mut name = sockaddr_in {
+        sin_family: 0,
+        sin_port: 0,
+        sin_addr: in_addr { s_addr: 0 },
+        sin_zero: [0; 8],
+    };
+
+    let result = if unsafe {
         connect(
             sock,
             &serv as *const sockaddr_in as *const sockaddr,
@@ -206,17 +369,9 @@ pub fn net_info() -> Result<Net, InfoError>
         )
     } == -1
     {
-        return Err(InfoError::General(format!("Unable to connect to '{IP}'")));
+        Err(InfoError::General(format!("Unable to connect to '{IP}'")))
     }
-
-    let mut name = sockaddr_in {
-        sin_family: 0,
-        sin_port: 0,
-        sin_addr: in_addr { s_addr: 0 },
-        sin_zero: [0; 8],
-    };
-
-    if unsafe {
+    else if unsafe {
         getsockname(
             sock,
             &mut name as *mut sockaddr_in as *mut sockaddr,
@@ -224,11 +379,13 @@ pub fn net_info() -> Result<Net, InfoError>
         )
     } == -1
     {
-        return Err(InfoError::General("Unable to get socket name".to_string()));
+        Err(InfoError::General("Unable to get socket name".to_string()))
     }
+    else
+    {
+        Ok(common::int_to_ipv4(name.sin_addr.s_addr))
+    };
 
     unsafe { close(sock) };
-    Ok(Net {
-        local_ip: common::int_to_ipv4(name.sin_addr.s_addr),
-    })
+    result
 }
@@ -1,65 +1,287 @@
 use super::*;
 
-pub fn os_info() -> Result<(String, crate::printing::OsArt), InfoError>
+/// The identifying fields of `/etc/os-release`
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub struct OsRelease
 {
-    let name = os_name()?;
-    let art = os_art(&name)?;
-    Ok((name, art))
+    /// `NAME`, e.g. `Ubuntu`
+    pub name: String,
+
+    /// `ID`, e.g. `ubuntu`
+    pub id: String,
+
+    /// `ID_LIKE`, the distributions this one is derived from (closest first)
+    pub id_like: Vec<String>,
+
+    /// `VERSION_ID`, e.g. `22.04`
+    pub version_id: Option<String>,
+
+    /// `PRETTY_NAME`, e.g. `Ubuntu 22.04.3 LTS`
+    pub pretty_name: Option<String>,
+
+    /// `VERSION_CODENAME`, e.g. `jammy`
+    pub version_codename: Option<String>,
+
+    /// `BUILD_ID`, used by rolling releases
+    pub build_id: Option<String>,
+
+    /// `VARIANT`, e.g. `Workstation Edition`
+    pub variant: Option<String>,
+
+    /// `LOGO`, the name of the distribution's logo icon, e.g.
+    /// `fedora-logo-icon`
+    pub logo: Option<String>,
 }
 
-pub fn os_name() -> Result<String, InfoError>
+impl OsRelease
 {
-    let mut release_b = String::new();
+    /// Parse the contents of an `os-release` file
+    pub fn parse(contents: &str) -> Self
+    {
+        let fields: HashMap<&str, String> = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), unquote(value.trim())))
+            .collect();
+        let field = |key: &str| fields.get(key).filter(|value| !value.is_empty()).cloned();
 
-    let os_name = match File::open(ETC_OS_RELEASE)
-        .and_then(|mut f| f.read_to_string(&mut release_b))
+        Self {
+            // `NAME` and `ID` default to "Linux" and "linux" when unset
+            name: field("NAME").unwrap_or_else(|| "Linux".to_string()),
+            id: field("ID").unwrap_or_else(|| "linux".to_string()),
+            id_like: field("ID_LIKE")
+                .map(|ids| ids.split_whitespace().map(str::to_string).collect())
+                .unwrap_or_default(),
+            version_id: field("VERSION_ID"),
+            pretty_name: field("PRETTY_NAME"),
+            version_codename: field("VERSION_CODENAME"),
+            build_id: field("BUILD_ID"),
+            variant: field("VARIANT"),
+            logo: field("LOGO"),
+        }
+    }
+
+    /// Build the equivalent from the contents of an `lsb-release` file
+    pub fn parse_lsb(contents: &str) -> Self
     {
-        Ok(_) =>
+        let fields: HashMap<&str, String> = contents
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), unquote(value.trim())))
+            .collect();
+        let field = |key: &str| fields.get(key).filter(|value| !value.is_empty()).cloned();
+
+        let id = field("DISTRIB_ID");
+        Self {
+            name: id.clone().unwrap_or_else(|| "Linux".to_string()),
+            id: id
+                .map(|id| id.to_lowercase())
+                .unwrap_or_else(|| "linux".to_string()),
+            version_id: field("DISTRIB_RELEASE"),
+            pretty_name: field("DISTRIB_DESCRIPTION"),
+            version_codename: field("DISTRIB_CODENAME"),
+            ..Default::default()
+        }
+    }
+
+    /// The name to print: `PRETTY_NAME`, or else `NAME` and `VERSION_ID`
+    pub fn display_name(&self) -> String
+    {
+        match (&self.pretty_name, &self.version_id)
         {
-            let name = release_b
-                .split('\n')
-                .find(|line| line.starts_with("NAME"))
-                .unwrap_or_default()
-                .split('=')
-                .last()
-                .unwrap_or_default()
-                .replace('"', "");
-            name.trim().to_string()
+            (Some(pretty_name), _) => pretty_name.clone(),
+            (None, Some(version)) => format!("{} {version}", self.name),
+            (None, None) => self.name.clone(),
         }
-        Err(_) =>
+    }
+}
+
+/// Remove the shell-style quoting from an `os-release` value
+fn unquote(value: &str) -> String
+{
+    let quoted = value.len() >= 2
+        && ((value.starts_with('"') && value.ends_with('"'))
+            || (value.starts_with('\'') && value.ends_with('\'')));
+    if !quoted
+    {
+        return value.to_string();
+    }
+
+    let mut unquoted = String::with_capacity(value.len());
+    let mut chars = value[1..value.len() - 1].chars();
+    while let Some(c) = chars.next()
+    {
+        match c
         {
-            if File::open(ETC_LSB_RELEASE)
-                .and_then(|mut f| f.read_to_string(&mut release_b))
-                .is_err()
-            {
-                return Err(InfoError::FileRead {
-                    path: ETC_LSB_RELEASE.to_string(),
-                });
-            }
+            '\\' => unquoted.extend(chars.next()),
+            c => unquoted.push(c),
+        }
+    }
+    unquoted
+}
+
+pub fn os_info() -> Result<(String, crate::printing::OsArt), InfoError>
+{
+    let release = os_release()?;
+    Ok((release.display_name(), os_art(&release)))
+}
 
-            let name = release_b
-                .split('\n')
-                .find(|line| line.starts_with("DISTRIB_DESCRIPTION"))
-                .unwrap_or_default()
-                .split('=')
-                .last()
-                .unwrap_or_default()
-                .replace('"', "");
-            name.trim().to_string()
+/// Read `/etc/os-release`, falling back to `/usr/lib/os-release` and then
+/// `/etc/lsb-release`
+pub fn os_release() -> Result<OsRelease, InfoError>
+{
+    let mut release_b = String::new();
+
+    for path in [ETC_OS_RELEASE, USR_LIB_OS_RELEASE]
+    {
+        if File::open(path)
+            .and_then(|mut f| f.read_to_string(&mut release_b))
+            .is_ok()
+        {
+            return Ok(OsRelease::parse(&release_b));
         }
-    };
+    }
+
+    if File::open(ETC_LSB_RELEASE)
+        .and_then(|mut f| f.read_to_string(&mut release_b))
+        .is_err()
+    {
+        return Err(InfoError::FileRead {
+            path: ETC_LSB_RELEASE.to_string(),
+        });
+    }
 
-    Ok(os_name)
+    Ok(OsRelease::parse_lsb(&release_b))
+}
+
+/// Choose the art for the distribution by its `ID`, falling back to the
+/// distributions in `ID_LIKE` so derivatives get their parent's art
+pub fn os_art(release: &OsRelease) -> crate::printing::OsArt
+{
+    std::iter::once(&release.id)
+        .chain(&release.id_like)
+        .find_map(|id| os_art_by_id(id))
+        .unwrap_or_default()
 }
 
-pub fn os_art(name: &str) -> Result<crate::printing::OsArt, InfoError>
+fn os_art_by_id(id: &str) -> Option<crate::printing::OsArt>
 {
     use printing::OsArt;
-    Ok(match name
+    Some(match id
     {
-        "Arch Linux" => OsArt::ArchLinux,
-        "Alpine Linux" => OsArt::AlpineLinux,
-        "Debian GNU/Linux" => OsArt::Debian,
-        _ => OsArt::Unknown,
+        "arch" => OsArt::ArchLinux,
+        "alpine" => OsArt::AlpineLinux,
+        "debian" => OsArt::Debian,
+        "ubuntu" => OsArt::Ubuntu,
+        "fedora" => OsArt::Fedora,
+        "opensuse"
+        | "opensuse-leap"
+        | "opensuse-tumbleweed"
+        | "opensuse-microos"
+        | "suse"
+        | "sles" => OsArt::OpenSuse,
+        "nixos" => OsArt::NixOs,
+        "gentoo" => OsArt::Gentoo,
+        "void" => OsArt::Void,
+        "manjaro" | "manjaro-arm" => OsArt::Manjaro,
+        "linuxmint" => OsArt::LinuxMint,
+        "pop" => OsArt::PopOs,
+        "rocky" => OsArt::RockyLinux,
+        "almalinux" => OsArt::AlmaLinux,
+        "rhel" => OsArt::Rhel,
+        "centos" => OsArt::CentOs,
+        "kali" => OsArt::KaliLinux,
+        "elementary" => OsArt::Elementary,
+        "raspbian" => OsArt::RaspberryPiOs,
+        "freebsd" => OsArt::FreeBsd,
+        _ => return None,
     })
 }
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn unquotes_values()
+    {
+        for (value, unquoted) in [
+            ("Fedora", "Fedora"),
+            ("\"Fedora Linux\"", "Fedora Linux"),
+            ("'Fedora Linux'", "Fedora Linux"),
+            ("\"Say \\\"hi\\\"\"", "Say \"hi\""),
+            ("\"back\\\\slash \\$HOME\"", "back\\slash $HOME"),
+            ("\"", "\""),
+            ("\"unterminated", "\"unterminated"),
+            ("\"\"", ""),
+        ]
+        {
+            assert_eq!(unquote(value), unquoted, "{value}");
+        }
+    }
+
+    #[test]
+    fn parses_os_release()
+    {
+        let release = OsRelease::parse(concat!(
+            "# Written by the distribution\n",
+            "NAME=\"Linux Mint\"\n",
+            "VERSION=\"21.2 (Victoria)\"\n",
+            "ID=linuxmint\n",
+            "ID_LIKE=\"ubuntu debian\"\n",
+            "  PRETTY_NAME = \"Linux Mint 21.2\"\n",
+            "VERSION_ID=\"21.2\"\n",
+            "VERSION_CODENAME=victoria\n",
+            "BUILD_ID=\n",
+            "VARIANT=\"Cinnamon Edition\"\n",
+            "#LOGO=commented-out\n",
+            "LOGO=\n",
+            "not a field\n",
+        ));
+        assert_eq!(
+            release,
+            OsRelease {
+                name: "Linux Mint".to_string(),
+                id: "linuxmint".to_string(),
+                id_like: vec!["ubuntu".to_string(), "debian".to_string()],
+                version_id: Some("21.2".to_string()),
+                pretty_name: Some("Linux Mint 21.2".to_string()),
+                version_codename: Some("victoria".to_string()),
+                build_id: None,
+                variant: Some("Cinnamon Edition".to_string()),
+                logo: None,
+            }
+        );
+        assert_eq!(release.display_name(), "Linux Mint 21.2");
+
+        // Unset `NAME` and `ID` have defaults
+        let release = OsRelease::parse("VERSION_ID=1.0\n");
+        assert_eq!(
+            (release.name.as_str(), release.id.as_str()),
+            ("Linux", "linux")
+        );
+        assert_eq!(release.display_name(), "Linux 1.0");
+    }
+
+    #[test]
+    fn parses_lsb_release()
+    {
+        let release = OsRelease::parse_lsb(concat!(
+            "DISTRIB_ID=Ubuntu\n",
+            "DISTRIB_RELEASE=22.04\n",
+            "DISTRIB_CODENAME=jammy\n",
+            "DISTRIB_DESCRIPTION=\"Ubuntu 22.04.3 LTS\"\n",
+        ));
+        assert_eq!(release.name, "Ubuntu");
+        assert_eq!(release.id, "ubuntu");
+        assert_eq!(release.version_id.as_deref(), Some("22.04"));
+        assert_eq!(release.version_codename.as_deref(), Some("jammy"));
+        assert_eq!(release.display_name(), "Ubuntu 22.04.3 LTS");
+        assert_eq!(os_art(&release), printing::OsArt::Ubuntu);
+
+        assert_eq!(OsRelease::parse_lsb("").display_name(), "Linux");
+    }
+}
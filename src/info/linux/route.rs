@@ -0,0 +1,234 @@
+use super::*;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// `RTF_UP`: the route is usable
+const RTF_UP: u32 = 0x0001;
+/// `RTF_GATEWAY`: the destination is a gateway
+const RTF_GATEWAY: u32 = 0x0002;
+
+/// The stub resolver systemd-resolved puts in `/etc/resolv.conf`
+const RESOLVED_STUB: &str = "127.0.0.53";
+
+pub fn route_info() -> Result<Route, InfoError>
+{
+    let mut gateways = default_routes()?;
+    gateways.extend(default_routes_v6());
+
+    let (nameservers, search) = resolv_conf();
+
+    Ok(Route {
+        gateways,
+        nameservers,
+        search,
+    })
+}
+
+/// Get the IPv4 default routes from `/proc/net/route`, best (lowest metric)
+/// first
+pub fn default_routes() -> Result<Vec<Gateway>, InfoError>
+{
+    let mut routes = String::new();
+
+    if File::open(PROC_NET_ROUTE)
+        .and_then(|mut f| f.read_to_string(&mut routes))
+        .is_err()
+    {
+        return Err(InfoError::FileRead {
+            path: PROC_NET_ROUTE.to_string(),
+        });
+    }
+
+    Ok(parse_routes(&routes))
+}
+
+fn parse_routes(routes: &str) -> Vec<Gateway>
+{
+    let mut gateways: Vec<Gateway> = routes
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            // Iface Destination Gateway Flags RefCnt Use Metric Mask ...
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let hex = |i: usize| u32::from_str_radix(fields.get(i)?, 16).ok();
+            let (destination, gateway, flags, metric, mask) =
+                (hex(1)?, hex(2)?, hex(3)?, fields.get(6)?, hex(7)?);
+
+            if destination != 0
+                || mask != 0
+                || flags & (RTF_UP | RTF_GATEWAY) != RTF_UP | RTF_GATEWAY
+            {
+                return None;
+            }
+
+            // Addresses are printed as the raw (network order) `u32`
+            Some(Gateway {
+                interface: fields[0].to_string(),
+                address: IpAddr::V4(Ipv4Addr::from(gateway.to_ne_bytes())),
+                metric: metric.parse().ok()?,
+            })
+        })
+        .collect();
+    gateways.sort_by_key(|gateway| gateway.metric);
+    gateways
+}
+
+/// Get the IPv6 default routes from `/proc/net/ipv6_route`. IPv6 may be
+/// disabled, in which case there are none.
+fn default_routes_v6() -> Vec<Gateway>
+{
+    let mut routes = String::new();
+    if File::open(PROC_NET_IPV6_ROUTE)
+        .and_then(|mut f| f.read_to_string(&mut routes))
+        .is_err()
+    {
+        return Vec::new();
+    }
+
+    parse_ipv6_routes(&routes)
+}
+
+fn parse_ipv6_routes(routes: &str) -> Vec<Gateway>
+{
+    let mut gateways: Vec<Gateway> = routes
+        .lines()
+        .filter_map(|line| {
+            // dest dest_len src src_len next_hop metric refcnt use flags iface
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (destination, prefix, next_hop) = (fields.first()?, fields.get(1)?, fields.get(4)?);
+            let metric = u32::from_str_radix(fields.get(5)?, 16).ok()?;
+            let flags = u32::from_str_radix(fields.get(8)?, 16).ok()?;
+
+            if *prefix != "00"
+                || destination.chars().any(|c| c != '0')
+                || flags & (RTF_UP | RTF_GATEWAY) != RTF_UP | RTF_GATEWAY
+            {
+                return None;
+            }
+
+            let next_hop = u128::from_str_radix(next_hop, 16).ok()?;
+            Some(Gateway {
+                interface: fields.get(9)?.to_string(),
+                address: IpAddr::V6(Ipv6Addr::from(next_hop)),
+                metric,
+            })
+        })
+        .collect();
+    gateways.sort_by_key(|gateway| gateway.metric);
+    gateways
+}
+
+/// Get the nameservers and search domains. When `/etc/resolv.conf` only points
+/// at systemd-resolved's stub, read the upstream servers it uses instead.
+fn resolv_conf() -> (Vec<String>, Vec<String>)
+{
+    let (nameservers, search) = read_resolv_conf(ETC_RESOLV_CONF);
+
+    if nameservers
+        .iter()
+        .all(|nameserver| nameserver == RESOLVED_STUB)
+    {
+        let (upstream, upstream_search) = read_resolv_conf(RUN_RESOLVED_RESOLV_CONF);
+        if !upstream.is_empty()
+        {
+            return (upstream, upstream_search);
+        }
+    }
+
+    (nameservers, search)
+}
+
+fn read_resolv_conf(path: &str) -> (Vec<String>, Vec<String>)
+{
+    let mut resolv = String::new();
+    if File::open(path)
+        .and_then(|mut f| f.read_to_string(&mut resolv))
+        .is_err()
+    {
+        return (Vec::new(), Vec::new());
+    }
+
+    parse_resolv_conf(&resolv)
+}
+
+fn parse_resolv_conf(resolv: &str) -> (Vec<String>, Vec<String>)
+{
+    let (mut nameservers, mut search) = (Vec::new(), Vec::new());
+    for line in resolv.lines()
+    {
+        let mut fields = line.split_whitespace();
+        match fields.next()
+        {
+            Some("nameserver") => nameservers.extend(fields.next().map(str::to_string)),
+            // The last `search` or `domain` line wins
+            Some("search" | "domain") => search = fields.map(str::to_string).collect(),
+            _ => (),
+        }
+    }
+
+    (nameservers, search)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// IPv4 addresses are as a little-endian machine prints them
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn parses_default_routes()
+    {
+        let gateways = parse_routes(concat!(
+            "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT\n",
+            "wlan0\t00000000\t0101A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0\n",
+            "eth0\t00000000\t0100000A\t0003\t0\t0\t100\t00000000\t0\t0\t0\n",
+            "eth0\t0000000A\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0\n",
+            "tun0\t00000000\t00000000\t0001\t0\t0\t50\t00000000\t0\t0\t0\n",
+        ));
+        assert_eq!(
+            gateways,
+            vec![
+                Gateway {
+                    interface: "eth0".to_string(),
+                    address: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+                    metric: 100,
+                },
+                Gateway {
+                    interface: "wlan0".to_string(),
+                    address: IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)),
+                    metric: 600,
+                },
+            ]
+        );
+
+        let gateways = parse_ipv6_routes(concat!(
+            "fd000000000000000000000000000000 40 00000000000000000000000000000000 00 ",
+            "00000000000000000000000000000000 00000100 00000001 00000000 00000001     eth0\n",
+            "00000000000000000000000000000000 00 00000000000000000000000000000000 00 ",
+            "fe800000000000000000000000000001 00000400 00000001 00000000 00000003     eth0\n",
+        ));
+        assert_eq!(
+            gateways,
+            vec![Gateway {
+                interface: "eth0".to_string(),
+                address: "fe80::1".parse().unwrap(),
+                metric: 1024,
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_resolv_conf()
+    {
+        let (nameservers, search) = parse_resolv_conf(concat!(
+            "# Generated by NetworkManager\n",
+            "domain example.org\n",
+            "search lan example.com\n",
+            "nameserver 192.168.1.1\n",
+            "nameserver 2001:db8::1\n",
+            "options edns0\n",
+        ));
+        assert_eq!(nameservers, vec!["192.168.1.1", "2001:db8::1"]);
+        assert_eq!(search, vec!["lan", "example.com"]);
+    }
+}
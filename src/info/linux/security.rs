@@ -0,0 +1,158 @@
+use super::*;
+use std::path::Path;
+
+pub fn security_info() -> Result<Security, InfoError>
+{
+    let boot_mode = boot_mode(Path::new(SYS_FIRMWARE));
+    let secure_boot = match boot_mode
+    {
+        BootMode::Uefi => secure_boot(),
+        _ => None,
+    };
+
+    Ok(Security {
+        selinux: selinux_mode(Path::new(SYS_SELINUX_ENFORCE)),
+        apparmor: apparmor_enabled(),
+        apparmor_profiles: apparmor_profiles(),
+        lockdown: lockdown_mode(Path::new(SYS_LOCKDOWN)),
+        boot_mode,
+        secure_boot,
+        vulnerabilities: vulnerabilities(),
+    })
+}
+
+/// Read a small sysfs file, trimmed
+fn read_trimmed(path: impl AsRef<Path>) -> Option<String>
+{
+    let mut contents = String::new();
+    File::open(path)
+        .and_then(|mut f| f.read_to_string(&mut contents))
+        .ok()?;
+    Some(contents.trim().to_string())
+}
+
+/// How the system booted, from `firmware` (`/sys/firmware`): UEFI firmware
+/// shows up in its `efi` directory. Containers often don't mount it at all, so
+/// then there's no telling.
+fn boot_mode(firmware: &Path) -> BootMode
+{
+    if firmware.join("efi").is_dir()
+    {
+        BootMode::Uefi
+    }
+    else if firmware.is_dir()
+    {
+        BootMode::Legacy
+    }
+    else
+    {
+        BootMode::Unknown
+    }
+}
+
+/// Get the SELinux mode from its `enforce` file, if SELinux is enabled
+fn selinux_mode(enforce: &Path) -> Option<String>
+{
+    let mode = match read_trimmed(enforce)?.as_str()
+    {
+        "1" => "enforcing",
+        _ => "permissive",
+    };
+    Some(mode.to_string())
+}
+
+fn apparmor_enabled() -> bool
+{
+    Path::new(SYS_APPARMOR).is_dir() || read_trimmed(SYS_APPARMOR_ENABLED).as_deref() == Some("Y")
+}
+
+/// Count the loaded AppArmor profiles. This is usually only readable by root.
+fn apparmor_profiles() -> Option<usize>
+{
+    let profiles = read_trimmed(format!("{SYS_APPARMOR}/profiles"))?;
+    Some(profiles.lines().count())
+}
+
+/// Get the selected kernel lockdown mode, e.g. `integrity` from
+/// `none [integrity] confidentiality`
+fn lockdown_mode(lockdown: &Path) -> Option<String>
+{
+    let lockdown = read_trimmed(lockdown)?;
+    let (_, selected) = lockdown.split_once('[')?;
+    let (selected, _) = selected.split_once(']')?;
+    Some(selected.to_string())
+}
+
+/// Decode the `SecureBoot` EFI variable: four bytes of attributes followed by
+/// a one byte boolean
+fn secure_boot() -> Option<bool>
+{
+    let mut efivar = Vec::new();
+    File::open(SYS_EFIVAR_SECURE_BOOT)
+        .and_then(|mut f| f.read_to_end(&mut efivar))
+        .ok()?;
+    efivar.get(4).map(|&enabled| enabled == 1)
+}
+
+/// Get the kernel's assessment of each known CPU vulnerability
+fn vulnerabilities() -> Vec<(String, String)>
+{
+    // Older kernels don't report vulnerabilities at all
+    let Ok(dir) = std::fs::read_dir(SYS_CPU_VULNERABILITIES)
+    else
+    {
+        return Vec::new();
+    };
+
+    let mut vulnerabilities: Vec<(String, String)> = dir
+        .flatten()
+        .filter_map(|entry| {
+            let status = read_trimmed(entry.path())?;
+            Some((entry.file_name().to_string_lossy().to_string(), status))
+        })
+        .collect();
+    vulnerabilities.sort();
+
+    vulnerabilities
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn reads_security_files()
+    {
+        let root = env::temp_dir().join(format!("rinfo-security-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("firmware/efi")).unwrap();
+        std::fs::create_dir_all(root.join("bios")).unwrap();
+        let file = |name: &str, contents: &str| {
+            let path = root.join(name);
+            std::fs::write(&path, contents).unwrap();
+            path
+        };
+
+        assert_eq!(
+            selinux_mode(&file("enforce", "1\n")).as_deref(),
+            Some("enforcing")
+        );
+        assert_eq!(
+            selinux_mode(&file("enforce", "0\n")).as_deref(),
+            Some("permissive")
+        );
+        assert_eq!(selinux_mode(&root.join("missing")), None);
+
+        let lockdown = file("lockdown", "none [integrity] confidentiality\n");
+        assert_eq!(lockdown_mode(&lockdown).as_deref(), Some("integrity"));
+        let lockdown = file("lockdown", "[none] integrity confidentiality\n");
+        assert_eq!(lockdown_mode(&lockdown).as_deref(), Some("none"));
+        assert_eq!(lockdown_mode(&file("lockdown", "none\n")), None);
+
+        assert_eq!(boot_mode(&root.join("firmware")), BootMode::Uefi);
+        assert_eq!(boot_mode(&root.join("bios")), BootMode::Legacy);
+        assert_eq!(boot_mode(&root.join("missing")), BootMode::Unknown);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,71 @@
+use super::*;
+use std::time::{Duration, Instant};
+
+/// Sample the traffic counters of every interface (except loopback) twice,
+/// `interval` apart
+pub fn throughput_info(interval: Duration) -> Result<Vec<Throughput>, InfoError>
+{
+    let first = interface_counters()?;
+    let start = Instant::now();
+    std::thread::sleep(interval);
+    let second = interface_counters()?;
+    let elapsed = start.elapsed().as_secs_f64();
+
+    Ok(second
+        .into_iter()
+        .filter(|(interface, _)| interface != "lo")
+        .map(|(interface, (rx_total, tx_total))| {
+            let (rx_before, tx_before) = first
+                .iter()
+                .find(|(name, _)| *name == interface)
+                .map(|(_, counters)| *counters)
+                .unwrap_or((rx_total, tx_total));
+
+            Throughput {
+                rx_rate: (rx_total.saturating_sub(rx_before) as f64 / elapsed) as u64,
+                tx_rate: (tx_total.saturating_sub(tx_before) as f64 / elapsed) as u64,
+                interface,
+                rx_total,
+                tx_total,
+            }
+        })
+        .collect())
+}
+
+/// Each interface's `(received, transmitted)` byte counters
+type Counters = Vec<(String, (u64, u64))>;
+
+/// Read the received and transmitted byte counters of each interface from
+/// `/proc/net/dev`
+fn interface_counters() -> Result<Counters, InfoError>
+{
+    let mut dev = String::new();
+
+    if File::open(PROC_NET_DEV)
+        .and_then(|mut f| f.read_to_string(&mut dev))
+        .is_err()
+    {
+        return Err(InfoError::FileRead {
+            path: PROC_NET_DEV.to_string(),
+        });
+    }
+
+    // The first two lines are headers, then e.g.
+    // `  eth0:  280058      62    0    0    0     0          0         0     7188
+    // ...`
+    Ok(dev
+        .lines()
+        .skip(2)
+        .filter_map(|line| {
+            let (interface, counters) = line.split_once(':')?;
+            let counters: Vec<u64> = counters
+                .split_whitespace()
+                .filter_map(|counter| counter.parse().ok())
+                .collect();
+            Some((
+                interface.trim().to_string(),
+                (*counters.first()?, *counters.get(8)?),
+            ))
+        })
+        .collect())
+}
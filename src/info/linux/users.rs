@@ -0,0 +1,121 @@
+use super::*;
+
+/// The size of a glibc `struct utmpx` record. musl's differs (its
+/// `ut_session` and `ut_tv` fields are `long`s on 64-bit targets), so these
+/// offsets are only used with glibc.
+#[cfg(target_env = "gnu")]
+const UTMP_RECORD_SIZE: usize = 384;
+
+/// `ut_type` of a normal login session
+#[cfg(target_env = "gnu")]
+const USER_PROCESS: i16 = 7;
+
+// Field offsets within a glibc `struct utmpx` record
+#[cfg(target_env = "gnu")]
+const UT_TYPE: usize = 0;
+#[cfg(target_env = "gnu")]
+const UT_LINE: (usize, usize) = (8, 32);
+#[cfg(target_env = "gnu")]
+const UT_USER: (usize, usize) = (44, 32);
+#[cfg(target_env = "gnu")]
+const UT_HOST: (usize, usize) = (76, 256);
+#[cfg(target_env = "gnu")]
+const UT_TV_SEC: usize = 340;
+
+#[cfg(target_env = "gnu")]
+pub fn users_info() -> Result<Users, InfoError>
+{
+    let utmp = PathBuf::from(VAR_RUN_UTMP);
+
+    // Containers and minimal systems often don't keep a utmp file at all, which
+    // just means nobody is logged in
+    if !utmp.is_file()
+    {
+        return Ok(Users::default());
+    }
+
+    let mut records = Vec::new();
+    if File::open(&utmp)
+        .and_then(|mut f| f.read_to_end(&mut records))
+        .is_err()
+    {
+        return Err(InfoError::FileRead {
+            path: VAR_RUN_UTMP.to_string(),
+        });
+    }
+
+    Ok(Users {
+        sessions: parse_utmp(&records),
+    })
+}
+
+#[cfg(not(target_env = "gnu"))]
+pub fn users_info() -> Result<Users, InfoError>
+{
+    // TODO: Read musl's utmp layout
+    Err(InfoError::Unsupported("Logged-in users".to_string()))
+}
+
+/// Parse the logged-in user sessions from the binary records of a utmp file
+#[cfg(target_env = "gnu")]
+fn parse_utmp(records: &[u8]) -> Vec<Session>
+{
+    records
+        .chunks_exact(UTMP_RECORD_SIZE)
+        .filter(|record| i16::from_ne_bytes([record[UT_TYPE], record[UT_TYPE + 1]]) == USER_PROCESS)
+        .map(|record| {
+            let host = utmp_str(record, UT_HOST);
+            let login_time = i32::from_ne_bytes(
+                record[UT_TV_SEC..UT_TV_SEC + 4]
+                    .try_into()
+                    .unwrap_or_default(),
+            );
+
+            Session {
+                user: utmp_str(record, UT_USER),
+                tty: utmp_str(record, UT_LINE),
+                host: (!host.is_empty()).then_some(host),
+                login_time: login_time as i64,
+            }
+        })
+        .collect()
+}
+
+/// Read a NUL-padded string field from a utmp record
+#[cfg(target_env = "gnu")]
+fn utmp_str(record: &[u8], (offset, len): (usize, usize)) -> String
+{
+    let field = &record[offset..offset + len];
+    let end = field.iter().position(|&b| b == 0).unwrap_or(len);
+    String::from_utf8_lossy(&field[..end]).to_string()
+}
+
+#[cfg(all(test, target_env = "gnu"))]
+mod tests
+{
+    use super::*;
+
+    /// A session glibc's `pututxline` recorded for `alice` on `pts/0`, logged
+    /// in over SSH from 192.168.1.20 (on x86_64)
+    const SSH_SESSION: &[u8; UTMP_RECORD_SIZE] = include_bytes!("testdata/utmp");
+
+    #[test]
+    fn parses_utmp_records()
+    {
+        let session = Session {
+            user: "alice".to_string(),
+            tty: "pts/0".to_string(),
+            host: Some("192.168.1.20".to_string()),
+            login_time: 1_700_000_000,
+        };
+        assert_eq!(parse_utmp(SSH_SESSION), std::slice::from_ref(&session));
+
+        // Other record types (here `DEAD_PROCESS`) and partial records are
+        // skipped
+        let mut records = SSH_SESSION.to_vec();
+        records.extend_from_slice(SSH_SESSION);
+        records[UTMP_RECORD_SIZE] = 8;
+        records.extend_from_slice(&SSH_SESSION[..100]);
+        assert_eq!(parse_utmp(&records), [session]);
+    }
+}
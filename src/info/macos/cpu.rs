@@ -17,6 +17,8 @@ pub fn cpu_info() -> Result<Cpu, InfoError>
         clock_rate,
         cores,
         threads,
+        limit: None,
+        temperature: None,
     })
 }
 
@@ -96,5 +96,6 @@ pub fn memory_info() -> Result<Memory, InfoError>
         total,
         available,
         used: total - available,
+        limit: None,
     })
 }
@@ -242,5 +242,99 @@ pub fn net_info() -> Result<Net, InfoError>
     unsafe { close(sock) };
     Ok(Net {
         local_ip: common::int_to_ipv4(name.sin_addr.s_addr),
+        ..Default::default()
     })
 }
+
+pub fn load_info(_interval: Option<std::time::Duration>) -> Result<Load, InfoError>
+{
+    let mut average = [0.0; 3];
+
+    if unsafe { libc::getloadavg(average.as_mut_ptr(), 3) } < 0
+    {
+        return Err(InfoError::General("getloadavg failed".to_string()));
+    }
+
+    // TODO: Count processes and sample CPU utilisation using
+    // `host_processor_info`
+    Ok(Load {
+        average,
+        ..Default::default()
+    })
+}
+
+pub fn locale_info() -> Result<Locale, InfoError>
+{
+    let timezone = std::fs::read_link("/etc/localtime")
+        .ok()
+        .and_then(|target| {
+            let target = target.to_string_lossy().into_owned();
+            Some(target.split_once("zoneinfo/")?.1.to_string())
+        })
+        .unwrap_or_else(|| "UTC".to_string());
+
+    // TODO: Read the keyboard layout from `com.apple.HIToolbox.plist`
+    Ok(Locale {
+        lang: std::env::var("LANG").unwrap_or_else(|_| "C".to_string()),
+        timezone,
+        utc_offset: chrono::Local::now().format("%:z").to_string(),
+        ..Default::default()
+    })
+}
+
+pub fn users_info() -> Result<Users, InfoError>
+{
+    // TODO: Enumerate login sessions
+    Err(InfoError::Unsupported("Logged-in users".to_string()))
+}
+
+pub fn init_info() -> Result<Init, InfoError>
+{
+    Ok(Init {
+        kind: InitKind::Launchd,
+        ..Default::default()
+    })
+}
+
+pub fn security_info() -> Result<Security, InfoError>
+{
+    // TODO: Report System Integrity Protection (`csr_get_active_config`) and
+    // FileVault
+    Err(InfoError::Unsupported("The security posture".to_string()))
+}
+
+pub fn in_container() -> bool
+{
+    // TODO: Detect containers
+    false
+}
+
+pub fn devices_info() -> Result<Devices, InfoError>
+{
+    // TODO: Enumerate PCI and USB devices
+    Err(InfoError::Unsupported("The device inventory".to_string()))
+}
+
+pub fn audio_info() -> Result<Audio, InfoError>
+{
+    // TODO: List audio devices through Core Audio
+    Err(InfoError::Unsupported("Audio devices".to_string()))
+}
+
+pub fn route_info() -> Result<Route, InfoError>
+{
+    // TODO: Read the routing table and DNS configuration
+    Err(InfoError::Unsupported("The routing table".to_string()))
+}
+
+pub fn throughput_info(_interval: std::time::Duration) -> Result<Vec<Throughput>, InfoError>
+{
+    // TODO: Sample interface counters
+    Err(InfoError::Unsupported("Network throughput".to_string()))
+}
+
+pub fn cpu_temperature() -> Option<f64>
+{
+    // TODO: Read the SMC sensors
+    None
+}
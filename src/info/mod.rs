@@ -1,8 +1,11 @@
 use crate::printing;
-use humansize::{FormatSize, BINARY};
-use std::path::PathBuf;
+use humansize::{FormatSize, FormatSizeOptions, BINARY};
+use std::{net::IpAddr, path::PathBuf, time::Duration};
 use thiserror::Error;
 pub mod common;
+mod fields;
+pub mod wan;
+pub use fields::*;
 
 #[cfg(target_os = "linux")]
 pub mod linux;
@@ -53,6 +56,9 @@ pub enum InfoError
 
     #[error("Error: Unexpected Error: {0}")]
     General(String),
+
+    #[error("UnsupportedError: {0} can't be read on this platform")]
+    Unsupported(String),
 }
 
 impl InfoError
@@ -78,6 +84,17 @@ impl InfoError
             Ok(x) => x,
         }
     }
+
+    /// Like `report`, but information the platform can't read is `None`
+    /// rather than an error, so it's left out of the output
+    pub fn report_supported<T>(e: Result<T, Self>) -> Option<T>
+    {
+        match e
+        {
+            Err(Self::Unsupported(_)) => None,
+            e => Some(Self::report(e)),
+        }
+    }
 }
 
 pub trait Information
@@ -104,6 +121,29 @@ pub struct Cpu
 
     /// Cpu clock rate in Megahertz
     pub clock_rate: f64,
+
+    /// The number of CPUs a cgroup limits us to
+    pub limit: Option<f64>,
+
+    /// The package temperature in degrees Celsius, where it's known and was
+    /// asked for
+    pub temperature: Option<f64>,
+}
+
+impl Cpu
+{
+    /// Read the CPU information, also reading its temperature when
+    /// `temperature` is set. Finding the sensor scans every hwmon device, so
+    /// it's opt-in.
+    pub fn read_with_temperature(temperature: bool) -> Result<Self, InfoError>
+    {
+        let mut cpu = Self::read()?;
+        if temperature
+        {
+            cpu.temperature = system::cpu_temperature();
+        }
+        Ok(cpu)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default, Copy)]
@@ -117,6 +157,19 @@ pub struct Memory
 
     /// Used memory in Bytes
     pub used: u64,
+
+    /// The memory limit of the cgroup we're running in
+    pub limit: Option<MemoryLimit>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default, Copy)]
+pub struct MemoryLimit
+{
+    /// The cgroup's memory limit in Bytes
+    pub total: u64,
+
+    /// Memory used by the cgroup in Bytes
+    pub used: u64,
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Default, Copy)]
@@ -136,6 +189,147 @@ pub struct Net
 {
     /// The local IP address used to access the internet
     local_ip: String,
+
+    /// The connected Wi-Fi interface, if any
+    pub wireless: Option<Wireless>,
+
+    /// Bluetooth adapters
+    pub bluetooth: Vec<BluetoothAdapter>,
+
+    /// Per-interface throughput, when sampled
+    pub throughput: Vec<Throughput>,
+}
+
+impl Net
+{
+    /// The default interval throughput is sampled over
+    pub const DEFAULT_RATE_INTERVAL: Duration = Duration::from_millis(500);
+
+    /// Read the network information, also sampling each interface's
+    /// throughput over `interval` when it's given
+    pub fn sample(interval: Option<Duration>) -> Result<Self, InfoError>
+    {
+        let mut net = Self::read()?;
+        if let Some(interval) = interval
+        {
+            match system::throughput_info(interval)
+            {
+                Ok(throughput) => net.throughput = throughput,
+                // The rest of the network information is still worth printing
+                Err(e @ InfoError::Unsupported(_)) => eprintln!("{e}"),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(net)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub struct Throughput
+{
+    /// The network interface
+    pub interface: String,
+
+    /// Bytes received per second
+    pub rx_rate: u64,
+
+    /// Bytes transmitted per second
+    pub tx_rate: u64,
+
+    /// Bytes received since the interface came up
+    pub rx_total: u64,
+
+    /// Bytes transmitted since the interface came up
+    pub tx_total: u64,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct Gateway
+{
+    /// The interface the route goes through
+    pub interface: String,
+
+    /// The gateway's address
+    pub address: IpAddr,
+
+    /// The route's metric (lower is preferred)
+    pub metric: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub struct Route
+{
+    /// Default gateways, IPv4 then IPv6, each best first
+    pub gateways: Vec<Gateway>,
+
+    /// DNS nameservers
+    pub nameservers: Vec<String>,
+
+    /// DNS search domains
+    pub search: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub struct Wan
+{
+    /// The public IP address, if it could be looked up
+    pub address: Option<IpAddr>,
+}
+
+impl Wan
+{
+    /// Look up the public IP address from `endpoint`, caching the result for
+    /// `ttl`
+    pub fn lookup(endpoint: &str, ttl: Duration) -> Self
+    {
+        let cache = dirs::cache_dir().map(|dir| dir.join("rinfo").join("wan.toml"));
+        wan::wan_info(endpoint, ttl, cache.as_deref())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub struct Wireless
+{
+    /// The wireless interface (e.g. `wlan0`)
+    pub interface: String,
+
+    /// The link quality, out of 70
+    pub link_quality: u8,
+
+    /// The signal level in dBm
+    pub signal: i32,
+
+    /// The SSID of the connected network
+    pub ssid: Option<String>,
+
+    /// The channel frequency in MHz
+    pub frequency: Option<u32>,
+}
+
+impl Wireless
+{
+    /// The frequency band of the channel (e.g. `5 GHz`)
+    pub fn band(&self) -> Option<&'static str>
+    {
+        Some(match self.frequency?
+        {
+            2400..=2500 => "2.4 GHz",
+            4900..=5900 => "5 GHz",
+            5925..=7125 => "6 GHz",
+            57000..=71000 => "60 GHz",
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub struct BluetoothAdapter
+{
+    /// The adapter's name (e.g. `hci0`)
+    pub name: String,
+
+    /// Is the adapter powered on, if that could be read?
+    pub powered: Option<bool>,
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
@@ -179,6 +373,301 @@ pub struct OperatingSystem
     pub art: printing::OsArt,
 }
 
+/// Pressure stall averages, as the percentage of time some tasks were stalled
+#[derive(Debug, PartialEq, PartialOrd, Clone, Default, Copy)]
+pub struct PressureStall
+{
+    /// Average over the last 10 seconds
+    pub avg10: f64,
+
+    /// Average over the last 60 seconds
+    pub avg60: f64,
+
+    /// Average over the last 300 seconds
+    pub avg300: f64,
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Default, Copy)]
+pub struct Pressure
+{
+    /// CPU pressure
+    pub cpu: Option<PressureStall>,
+
+    /// Memory pressure
+    pub memory: Option<PressureStall>,
+
+    /// IO pressure
+    pub io: Option<PressureStall>,
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Default)]
+pub struct Load
+{
+    /// The 1, 5, and 15 minute load averages
+    pub average: [f64; 3],
+
+    /// Currently runnable processes, where they're counted
+    pub running: Option<usize>,
+
+    /// Total processes, where they're counted
+    pub total: Option<usize>,
+
+    /// Overall CPU utilisation (percent) over the sampling interval, when it
+    /// was sampled
+    pub cpu_usage: Option<f64>,
+
+    /// Pressure stall information, when the system provides it
+    pub pressure: Option<Pressure>,
+}
+
+impl Load
+{
+    /// The default interval CPU utilisation is sampled over
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(200);
+
+    /// Read the system load, and sample CPU utilisation over `interval` when
+    /// it's given. Sampling blocks for the whole interval, so it's opt-in.
+    pub fn sample(interval: Option<Duration>) -> Result<Self, InfoError>
+    {
+        if !system::initialized()
+        {
+            system::init()?;
+        }
+
+        system::load_info(interval)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub struct Locale
+{
+    /// The default locale (`LANG`)
+    pub lang: String,
+
+    /// Locale categories (e.g. `LC_TIME`) that resolve to something other
+    /// than `lang`
+    pub categories: Vec<(String, String)>,
+
+    /// The system timezone (e.g. `Europe/Berlin`)
+    pub timezone: String,
+
+    /// The current offset from UTC (e.g. `+02:00`)
+    pub utc_offset: String,
+
+    /// The virtual console keymap
+    pub console_keymap: Option<String>,
+
+    /// The X11 keyboard layout
+    pub x11_layout: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub struct Session
+{
+    /// The logged-in user
+    pub user: String,
+
+    /// The terminal the session is on (e.g. `pts/0`)
+    pub tty: String,
+
+    /// The remote host, for remote (e.g. SSH) sessions
+    pub host: Option<String>,
+
+    /// The login time as a Unix timestamp (seconds)
+    pub login_time: i64,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub struct Users
+{
+    /// Every active login session
+    pub sessions: Vec<Session>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub enum InitKind
+{
+    Systemd,
+    OpenRc,
+    Runit,
+    S6,
+    Dinit,
+    SysVinit,
+    Tini,
+    DumbInit,
+    #[cfg(target_os = "macos")]
+    Launchd,
+    #[cfg(target_os = "windows")]
+    Windows,
+
+    /// Some other process is PID 1 (e.g. a container's entrypoint)
+    Other(String),
+
+    #[default]
+    Unknown,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub struct Init
+{
+    /// The init system running as PID 1
+    pub kind: InitKind,
+
+    /// The service manager's state (e.g. `starting`, `running`)
+    pub state: Option<String>,
+
+    /// The number of failed units/services
+    pub failed_units: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Default, Copy)]
+pub enum BootMode
+{
+    Uefi,
+    Legacy,
+
+    #[default]
+    Unknown,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub struct Security
+{
+    /// The SELinux mode, if SELinux is enabled
+    pub selinux: Option<String>,
+
+    /// Is AppArmor enabled?
+    pub apparmor: bool,
+
+    /// The number of loaded AppArmor profiles, when readable
+    pub apparmor_profiles: Option<usize>,
+
+    /// The kernel lockdown mode
+    pub lockdown: Option<String>,
+
+    /// How the system was booted
+    pub boot_mode: BootMode,
+
+    /// Is Secure Boot enabled? `None` when unknown
+    pub secure_boot: Option<bool>,
+
+    /// CPU vulnerabilities and the kernel's mitigation status for each
+    pub vulnerabilities: Vec<(String, String)>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub struct PciDevice
+{
+    /// The PCI address (e.g. `0000:01:00.0`)
+    pub slot: String,
+
+    /// The vendor's name
+    pub vendor: String,
+
+    /// The device's name
+    pub device: String,
+
+    pub vendor_id: u16,
+    pub device_id: u16,
+
+    /// The device class's name (e.g. `Display controller`)
+    pub class: String,
+
+    /// The subclass's name (e.g. `VGA compatible controller`)
+    pub subclass: Option<String>,
+
+    /// The bound kernel driver
+    pub driver: Option<String>,
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Default)]
+pub struct UsbDevice
+{
+    /// The bus number
+    pub bus: u16,
+
+    /// The device's address on the bus
+    pub address: u16,
+
+    /// The vendor's name
+    pub vendor: String,
+
+    /// The product's name
+    pub product: String,
+
+    pub vendor_id: u16,
+    pub product_id: u16,
+
+    /// The device class's name (e.g. `Hub`)
+    pub class: String,
+
+    /// The subclass's name (e.g. `Unused`)
+    pub subclass: Option<String>,
+
+    /// The kernel drivers bound to the device's interfaces
+    pub drivers: Vec<String>,
+
+    /// The negotiated speed in Mbit/s (1.5 for low-speed devices)
+    pub speed: Option<f32>,
+}
+
+/// An inventory of the PCI and USB devices in the system
+#[derive(Debug, PartialEq, PartialOrd, Clone, Default)]
+pub struct Devices
+{
+    pub pci: Vec<PciDevice>,
+    pub usb: Vec<UsbDevice>,
+}
+
+impl Devices
+{
+    /// Keep only the devices whose class or subclass name contains `class`
+    /// (case-insensitive)
+    pub fn filter_class(&mut self, class: &str)
+    {
+        let class = class.to_lowercase();
+        let matches = |name: &String| name.to_lowercase().contains(&class);
+        self.pci
+            .retain(|device| matches(&device.class) || device.subclass.iter().any(matches));
+        self.usb
+            .retain(|device| matches(&device.class) || device.subclass.iter().any(matches));
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub struct SoundCard
+{
+    /// The card's index
+    pub index: usize,
+
+    /// The card's short ID (e.g. `PCH`)
+    pub id: String,
+
+    /// The card's display name
+    pub name: String,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Copy)]
+#[allow(dead_code)]
+pub enum SoundServer
+{
+    PipeWire,
+    PulseAudio,
+    Jack,
+    CoreAudio,
+    WindowsAudio,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub struct Audio
+{
+    /// The sound cards in the system
+    pub cards: Vec<SoundCard>,
+
+    /// The running sound servers
+    pub servers: Vec<SoundServer>,
+}
+
 impl Information for Net
 {
     fn read() -> Result<Self, InfoError>
@@ -272,6 +761,46 @@ impl Information for OsKind
     }
 }
 
+impl Information for Load
+{
+    fn read() -> Result<Self, InfoError> { Self::sample(None) }
+}
+
+impl Information for Locale
+{
+    fn read() -> Result<Self, InfoError> { system::locale_info() }
+}
+
+impl Information for Users
+{
+    fn read() -> Result<Self, InfoError> { system::users_info() }
+}
+
+impl Information for Init
+{
+    fn read() -> Result<Self, InfoError> { system::init_info() }
+}
+
+impl Information for Security
+{
+    fn read() -> Result<Self, InfoError> { system::security_info() }
+}
+
+impl Information for Devices
+{
+    fn read() -> Result<Self, InfoError> { system::devices_info() }
+}
+
+impl Information for Audio
+{
+    fn read() -> Result<Self, InfoError> { system::audio_info() }
+}
+
+impl Information for Route
+{
+    fn read() -> Result<Self, InfoError> { system::route_info() }
+}
+
 impl Information for Caller
 {
     fn read() -> Result<Self, InfoError> { system::caller_info() }
@@ -309,9 +838,19 @@ impl std::fmt::Display for Cpu
         use chrono::Duration;
         use chrono_humanize::{Accuracy, HumanTime, Tense};
         let uptime = HumanTime::from(Duration::milliseconds(-(self.uptime as i64)));
+        let limit = match self.limit
+        {
+            Some(limit) => format!(", cgroup limit {limit:.2} CPUs"),
+            None => String::new(),
+        };
+        let temperature = match self.temperature
+        {
+            Some(temperature) => format!(", {temperature:.1}°C"),
+            None => String::new(),
+        };
         write!(
             f,
-            "CPU: {}@{:.2}GHz ({} cores, {} threads)\nUPTIME: {}",
+            "CPU: {}@{:.2}GHz ({} cores, {} threads{limit}{temperature})\nUPTIME: {}",
             self.name,
             self.clock_rate / 1000.0,
             self.cores,
@@ -321,20 +860,41 @@ impl std::fmt::Display for Cpu
     }
 }
 
-impl std::fmt::Display for Memory
+impl Memory
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    /// Write the memory usage with sizes formatted by `format` (e.g. `BINARY`)
+    pub fn fmt_with(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        format: FormatSizeOptions,
+    ) -> std::fmt::Result
     {
+        if let Some(limit) = self.limit
+        {
+            return write!(
+                f,
+                "RAM: {}/{} (cgroup limit; host {})",
+                limit.used.format_size(format),
+                limit.total.format_size(format),
+                self.total.format_size(format),
+            );
+        }
+
         write!(
             f,
             "RAM: {}/{} ({} available)",
-            self.used.format_size(BINARY),
-            self.total.format_size(BINARY),
-            self.available.format_size(BINARY),
+            self.used.format_size(format),
+            self.total.format_size(format),
+            self.available.format_size(format),
         )
     }
 }
 
+impl std::fmt::Display for Memory
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { self.fmt_with(f, BINARY) }
+}
+
 impl std::fmt::Display for OperatingSystem
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
@@ -364,7 +924,58 @@ impl std::fmt::Display for Net
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
-        write!(f, "LAN: {} (IPV4)", self.local_ip)
+        write!(f, "LAN: {} (IPV4)", self.local_ip)?;
+
+        if let Some(wireless) = &self.wireless
+        {
+            write!(f, "\nWIFI: {}", wireless.interface)?;
+            if let Some(ssid) = &wireless.ssid
+            {
+                write!(f, " \"{ssid}\"")?;
+            }
+            if let (Some(band), Some(frequency)) = (wireless.band(), wireless.frequency)
+            {
+                write!(f, " {band} ({frequency} MHz)")?;
+            }
+            write!(
+                f,
+                ", {}% ({} dBm)",
+                wireless.link_quality as u32 * 100 / 70,
+                wireless.signal
+            )?;
+        }
+
+        if !self.bluetooth.is_empty()
+        {
+            let adapters: Vec<String> = self
+                .bluetooth
+                .iter()
+                .map(|adapter| {
+                    match adapter.powered
+                    {
+                        Some(true) => format!("{} (powered on)", adapter.name),
+                        Some(false) => format!("{} (powered off)", adapter.name),
+                        None => adapter.name.clone(),
+                    }
+                })
+                .collect();
+            write!(f, "\nBLUETOOTH: {}", adapters.join(", "))?;
+        }
+
+        for throughput in &self.throughput
+        {
+            write!(
+                f,
+                "\nTRAFFIC {}: RX {}/s, TX {}/s (total RX {}, TX {})",
+                throughput.interface,
+                throughput.rx_rate.format_size(BINARY),
+                throughput.tx_rate.format_size(BINARY),
+                throughput.rx_total.format_size(BINARY),
+                throughput.tx_total.format_size(BINARY),
+            )?;
+        }
+
+        Ok(())
     }
 }
 
@@ -375,3 +986,412 @@ impl std::fmt::Display for Caller
         write!(f, "USER: {}\nSHELL: {}", self.name, self.shell)
     }
 }
+
+impl std::fmt::Display for Load
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(
+            f,
+            "LOAD: {:.2}, {:.2}, {:.2}",
+            self.average[0], self.average[1], self.average[2]
+        )?;
+        if let (Some(running), Some(total)) = (self.running, self.total)
+        {
+            write!(f, " ({running}/{total} processes)")?;
+        }
+
+        if let Some(usage) = self.cpu_usage
+        {
+            write!(f, "\nCPU USAGE: {usage:.1}%")?;
+        }
+
+        if let Some(pressure) = self.pressure
+        {
+            let stalls: Vec<String> = [
+                ("cpu", pressure.cpu),
+                ("memory", pressure.memory),
+                ("io", pressure.io),
+            ]
+            .iter()
+            .filter_map(|(name, stall)| Some(format!("{name} {:.2}%", stall.as_ref()?.avg10)))
+            .collect();
+
+            write!(f, "\nPRESSURE: {} (avg10)", stalls.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Locale
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "LOCALE: {}", self.lang)?;
+        if !self.categories.is_empty()
+        {
+            let categories: Vec<String> = self
+                .categories
+                .iter()
+                .map(|(category, value)| format!("{category}={value}"))
+                .collect();
+            write!(f, " ({})", categories.join(", "))?;
+        }
+
+        write!(f, "\nTIMEZONE: {} (UTC{})", self.timezone, self.utc_offset)?;
+
+        let keyboard: Vec<String> = [(&self.console_keymap, "console"), (&self.x11_layout, "X11")]
+            .iter()
+            .filter_map(|(layout, source)| Some(format!("{} ({source})", layout.as_ref()?)))
+            .collect();
+        if !keyboard.is_empty()
+        {
+            write!(f, "\nKEYBOARD: {}", keyboard.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Users
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        use chrono::{TimeZone, Utc};
+        use chrono_humanize::{Accuracy, HumanTime, Tense};
+
+        let mut names: Vec<&str> = self.sessions.iter().map(|s| s.user.as_str()).collect();
+        names.sort_unstable();
+        names.dedup();
+
+        write!(f, "USERS: {}", self.sessions.len())?;
+        if !names.is_empty()
+        {
+            write!(f, " ({})", names.join(", "))?;
+        }
+
+        for session in &self.sessions
+        {
+            write!(f, "\n  {} on {}", session.user, session.tty)?;
+            if let Some(host) = &session.host
+            {
+                write!(f, " from {host}")?;
+            }
+            if let Some(login_time) = Utc.timestamp_opt(session.login_time, 0).single()
+            {
+                let since = HumanTime::from(login_time - Utc::now());
+                write!(f, ", {}", since.to_text_en(Accuracy::Rough, Tense::Past))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for InitKind
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let s = match self
+        {
+            Self::Systemd => "systemd",
+            Self::OpenRc => "OpenRC",
+            Self::Runit => "runit",
+            Self::S6 => "s6",
+            Self::Dinit => "dinit",
+            Self::SysVinit => "SysVinit",
+            Self::Tini => "tini",
+            Self::DumbInit => "dumb-init",
+            #[cfg(target_os = "macos")]
+            Self::Launchd => "launchd",
+            #[cfg(target_os = "windows")]
+            Self::Windows => "Service Control Manager",
+            Self::Other(name) => name,
+
+            _ => "Unknown",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl std::fmt::Display for Init
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "INIT: {}", self.kind)?;
+
+        let status: Vec<String> = [
+            self.state.clone(),
+            self.failed_units
+                .map(|failed| format!("{failed} failed services")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if !status.is_empty()
+        {
+            write!(f, " ({})", status.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for BootMode
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let s = match self
+        {
+            Self::Uefi => "UEFI",
+            Self::Legacy => "Legacy BIOS",
+
+            _ => "Unknown",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl std::fmt::Display for Security
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let mut modules = Vec::new();
+        if let Some(mode) = &self.selinux
+        {
+            modules.push(format!("SELinux {mode}"));
+        }
+        if self.apparmor
+        {
+            modules.push(match self.apparmor_profiles
+            {
+                Some(profiles) => format!("AppArmor ({profiles} profiles)"),
+                None => "AppArmor".to_string(),
+            });
+        }
+        if let Some(lockdown) = &self.lockdown
+        {
+            modules.push(format!("lockdown {lockdown}"));
+        }
+        if modules.is_empty()
+        {
+            modules.push("none".to_string());
+        }
+        write!(f, "SECURITY: {}", modules.join(", "))?;
+
+        write!(f, "\nBOOT: {}", self.boot_mode)?;
+        if let Some(secure_boot) = self.secure_boot
+        {
+            let state = if secure_boot { "enabled" } else { "disabled" };
+            write!(f, " (Secure Boot {state})")?;
+        }
+
+        if !self.vulnerabilities.is_empty()
+        {
+            let (mut unaffected, mut mitigated, mut unknown) = (0, 0, 0);
+            let mut vulnerable = Vec::new();
+            for (name, status) in &self.vulnerabilities
+            {
+                // e.g. `KVM: Mitigation: VMX disabled` for `itlb_multihit`
+                let status = status.strip_prefix("KVM: ").unwrap_or(status);
+                if status.starts_with("Not affected")
+                {
+                    unaffected += 1;
+                }
+                else if status.starts_with("Mitigation")
+                {
+                    mitigated += 1;
+                }
+                else if status.starts_with("Vulnerable")
+                {
+                    vulnerable.push(name.as_str());
+                }
+                else
+                {
+                    // e.g. `Unknown: Dependent on hypervisor status`
+                    unknown += 1;
+                }
+            }
+
+            write!(
+                f,
+                "\nCPU VULNERABILITIES: {unaffected} not affected, {mitigated} mitigated, "
+            )?;
+            if unknown > 0
+            {
+                write!(f, "{unknown} unknown, ")?;
+            }
+            write!(f, "{} vulnerable", vulnerable.len())?;
+            if !vulnerable.is_empty()
+            {
+                write!(f, " ({})", vulnerable.join(", "))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for PciDevice
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{} {}", self.slot, self.class)?;
+        if let Some(subclass) = &self.subclass
+        {
+            write!(f, " / {subclass}")?;
+        }
+        write!(
+            f,
+            ": {} {} [{:04x}:{:04x}]",
+            self.vendor, self.device, self.vendor_id, self.device_id
+        )?;
+        if let Some(driver) = &self.driver
+        {
+            write!(f, " (driver: {driver})")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for UsbDevice
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "{:03}:{:03} {}", self.bus, self.address, self.class)?;
+        if let Some(subclass) = &self.subclass
+        {
+            write!(f, " / {subclass}")?;
+        }
+        write!(
+            f,
+            ": {} {} [{:04x}:{:04x}]",
+            self.vendor, self.product, self.vendor_id, self.product_id
+        )?;
+
+        let mut details = Vec::new();
+        if let Some(speed) = self.speed
+        {
+            details.push(format!("{speed} Mbps"));
+        }
+        if !self.drivers.is_empty()
+        {
+            details.push(format!("driver: {}", self.drivers.join(", ")));
+        }
+        if !details.is_empty()
+        {
+            write!(f, " ({})", details.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Devices
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "PCI:")?;
+        for device in &self.pci
+        {
+            write!(f, "\n  {device}")?;
+        }
+
+        write!(f, "\nUSB:")?;
+        for device in &self.usb
+        {
+            write!(f, "\n  {device}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for SoundServer
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let s = match self
+        {
+            Self::PipeWire => "PipeWire",
+            Self::PulseAudio => "PulseAudio",
+            Self::Jack => "JACK",
+            Self::CoreAudio => "Core Audio",
+            Self::WindowsAudio => "Windows Audio",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl std::fmt::Display for Audio
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let cards: Vec<&str> = self.cards.iter().map(|card| card.name.as_str()).collect();
+        let servers: Vec<String> = self.servers.iter().map(ToString::to_string).collect();
+
+        let or_none = |list: String| {
+            if list.is_empty()
+            {
+                "none".to_string()
+            }
+            else
+            {
+                list
+            }
+        };
+        write!(
+            f,
+            "AUDIO: {}\nSOUND SERVER: {}",
+            or_none(cards.join(", ")),
+            or_none(servers.join(", "))
+        )
+    }
+}
+
+impl std::fmt::Display for Route
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let gateways: Vec<String> = self
+            .gateways
+            .iter()
+            .map(|gateway| format!("{} ({})", gateway.address, gateway.interface))
+            .collect();
+        let or_none = |list: String| {
+            if list.is_empty()
+            {
+                "none".to_string()
+            }
+            else
+            {
+                list
+            }
+        };
+
+        write!(
+            f,
+            "GATEWAY: {}\nDNS: {}",
+            or_none(gateways.join(", ")),
+            or_none(self.nameservers.join(", "))
+        )?;
+        if !self.search.is_empty()
+        {
+            write!(f, " (search {})", self.search.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Wan
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self.address
+        {
+            Some(IpAddr::V4(address)) => write!(f, "WAN: {address} (IPV4)"),
+            Some(IpAddr::V6(address)) => write!(f, "WAN: {address} (IPV6)"),
+            None => write!(f, "WAN: unavailable"),
+        }
+    }
+}
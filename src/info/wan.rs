@@ -0,0 +1,471 @@
+//! Look up the public (WAN) IP address by asking an HTTP or DNS endpoint what
+//! address our request came from
+
+use super::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{Read, Write},
+    net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs, UdpSocket},
+    path::Path,
+};
+
+/// Google's authoritative nameservers answer this TXT query with the address
+/// the query came from
+pub const DEFAULT_ENDPOINT: &str = "dns://ns1.google.com/o-o.myaddr.l.google.com";
+
+/// How long a looked up address is reused for
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// How long to wait on the endpoint
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// `TXT` DNS record type
+const DNS_TYPE_TXT: u16 = 16;
+
+/// A previously looked up address
+#[derive(Debug, Serialize, Deserialize)]
+struct Cache
+{
+    endpoint: String,
+    address: IpAddr,
+
+    /// When the address was looked up (Unix timestamp)
+    fetched: i64,
+}
+
+/// Get the public IP address, reusing the cached address if it was looked up
+/// from the same endpoint less than `ttl` ago
+pub fn wan_info(endpoint: &str, ttl: Duration, cache: Option<&Path>) -> Wan
+{
+    let now = chrono::Utc::now().timestamp();
+    let cached: Option<Cache> = cache
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .filter(|cached: &Cache| cached.endpoint == endpoint);
+
+    if let Some(cached) = &cached
+    {
+        if now - cached.fetched < ttl.as_secs() as i64
+        {
+            return Wan {
+                address: Some(cached.address),
+            };
+        }
+    }
+
+    match lookup(endpoint)
+    {
+        Ok(address) =>
+        {
+            if let Some(path) = cache
+            {
+                let cached = Cache {
+                    endpoint: endpoint.to_string(),
+                    address,
+                    fetched: now,
+                };
+                // Failing to cache only costs us another lookup next time
+                if let Ok(contents) = toml::to_string(&cached)
+                {
+                    let _ = path
+                        .parent()
+                        .map_or(Ok(()), std::fs::create_dir_all)
+                        .and_then(|_| std::fs::write(path, contents));
+                }
+            }
+            Wan {
+                address: Some(address),
+            }
+        }
+        Err(e) =>
+        {
+            eprintln!("{e}");
+            // A stale address is better than none
+            Wan {
+                address: cached.map(|cached| cached.address),
+            }
+        }
+    }
+}
+
+/// Ask an `http://host[:port]/path` or `dns://server[:port]/name` endpoint for
+/// our address
+pub fn lookup(endpoint: &str) -> Result<IpAddr, InfoError>
+{
+    let invalid = || InfoError::General(format!("Invalid WAN endpoint '{endpoint}'"));
+
+    let (scheme, rest) = endpoint.split_once("://").ok_or_else(invalid)?;
+    let (authority, path) = match rest.split_once('/')
+    {
+        Some((authority, path)) => (authority, format!("/{path}")),
+        None => (rest, "/".to_string()),
+    };
+
+    let answer = match scheme
+    {
+        "http" => http_get(authority, &path)?,
+        "dns" => dns_txt(authority, path.trim_start_matches('/'))?,
+        _ => return Err(invalid()),
+    };
+
+    answer.trim().parse().map_err(|_| {
+        InfoError::General(format!(
+            "WAN endpoint '{endpoint}' didn't answer with an IP address"
+        ))
+    })
+}
+
+/// Split `authority` into its host and port, if it has one. IPv6 addresses
+/// are bracketed when they have a port (e.g. `[2001:db8::1]:8053`).
+fn split_authority(authority: &str) -> (&str, Option<u16>)
+{
+    if let Some(rest) = authority.strip_prefix('[')
+    {
+        if let Some((host, port)) = rest.split_once(']')
+        {
+            return (
+                host,
+                port.strip_prefix(':').and_then(|port| port.parse().ok()),
+            );
+        }
+    }
+
+    match authority.rsplit_once(':')
+    {
+        // A bare IPv6 address has more than one colon and no port
+        Some((host, port)) if !host.contains(':') => (host, port.parse().ok()),
+        _ => (authority, None),
+    }
+}
+
+/// Resolve `authority`, using `default_port` if it doesn't have one
+fn resolve(authority: &str, default_port: u16) -> Result<Vec<SocketAddr>, InfoError>
+{
+    let (host, port) = split_authority(authority);
+    let addrs: Vec<SocketAddr> = (host, port.unwrap_or(default_port))
+        .to_socket_addrs()
+        .map(Iterator::collect)
+        .unwrap_or_default();
+
+    if addrs.is_empty()
+    {
+        return Err(InfoError::General(format!(
+            "Couldn't resolve '{authority}'"
+        )));
+    }
+    Ok(addrs)
+}
+
+/// Make a plain HTTP/1.0 GET request and return the body
+fn http_get(authority: &str, path: &str) -> Result<String, InfoError>
+{
+    let error = |e: std::io::Error| {
+        InfoError::General(format!("HTTP request to '{authority}' failed: {e}"))
+    };
+
+    // Try each address, since a dual-stack host's IPv6 address may not be
+    // reachable
+    let mut last_error = None;
+    let mut stream = None;
+    for addr in resolve(authority, 80)?
+    {
+        match TcpStream::connect_timeout(&addr, TIMEOUT)
+        {
+            Ok(connected) =>
+            {
+                stream = Some(connected);
+                break;
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    let Some(mut stream) = stream
+    else
+    {
+        return Err(error(last_error.expect("resolve returns an address")));
+    };
+    stream.set_read_timeout(Some(TIMEOUT)).map_err(error)?;
+    stream.set_write_timeout(Some(TIMEOUT)).map_err(error)?;
+
+    // HTTP/1.0 keeps the response simple: no chunked encoding or keep-alive
+    let host = match split_authority(authority).0
+    {
+        host if host.contains(':') => format!("[{host}]"),
+        host => host.to_string(),
+    };
+    let request = format!(
+        "GET {path} HTTP/1.0\r\nHost: {host}\r\nUser-Agent: rinfo\r\nAccept: text/plain\r\n\r\n"
+    );
+    stream.write_all(request.as_bytes()).map_err(error)?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).map_err(error)?;
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+    let status = head.lines().next().unwrap_or_default();
+    if status.split_whitespace().nth(1) != Some("200")
+    {
+        return Err(InfoError::General(format!(
+            "HTTP request to '{authority}' failed: {status}"
+        )));
+    }
+
+    Ok(body.to_string())
+}
+
+/// Query `server` for the first TXT record of `name`
+fn dns_txt(server: &str, name: &str) -> Result<String, InfoError>
+{
+    let error =
+        |e: std::io::Error| InfoError::General(format!("DNS query to '{server}' failed: {e}"));
+
+    // Try each address, since a dual-stack server's IPv6 address may not be
+    // reachable
+    let id = std::process::id() as u16;
+    let query = dns_query(id, name);
+    let mut last_error = None;
+    let mut response = None;
+    for addr in resolve(server, 53)?
+    {
+        match dns_exchange(addr, &query)
+        {
+            Ok(answer) =>
+            {
+                response = Some(answer);
+                break;
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    let Some(response) = response
+    else
+    {
+        return Err(error(last_error.expect("resolve returns an address")));
+    };
+
+    parse_dns_txt(&response, id).ok_or_else(|| {
+        InfoError::General(format!(
+            "DNS query to '{server}' didn't return a TXT record"
+        ))
+    })
+}
+
+/// Send `query` to the DNS server at `addr` over UDP and return its response
+fn dns_exchange(addr: SocketAddr, query: &[u8]) -> std::io::Result<Vec<u8>>
+{
+    let local: SocketAddr = if addr.is_ipv6()
+    {
+        (std::net::Ipv6Addr::UNSPECIFIED, 0).into()
+    }
+    else
+    {
+        (std::net::Ipv4Addr::UNSPECIFIED, 0).into()
+    };
+    let socket = UdpSocket::bind(local)?;
+    socket.set_read_timeout(Some(TIMEOUT))?;
+    socket.connect(addr)?;
+    socket.send(query)?;
+
+    let mut response = vec![0; 512];
+    let len = socket.recv(&mut response)?;
+    response.truncate(len);
+    Ok(response)
+}
+
+/// Build a recursive TXT query for `name`
+fn dns_query(id: u16, name: &str) -> Vec<u8>
+{
+    let mut query = Vec::with_capacity(32 + name.len());
+    query.extend_from_slice(&id.to_be_bytes());
+    // Recursion desired, one question
+    query.extend_from_slice(&[0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    for label in name.trim_end_matches('.').split('.')
+    {
+        query.push(label.len() as u8);
+        query.extend_from_slice(label.as_bytes());
+    }
+    query.push(0);
+    query.extend_from_slice(&DNS_TYPE_TXT.to_be_bytes());
+    query.extend_from_slice(&1u16.to_be_bytes()); // IN
+    query
+}
+
+/// Get the first string of the first TXT record in a DNS response
+fn parse_dns_txt(response: &[u8], id: u16) -> Option<String>
+{
+    let u16_at = |i: usize| Some(u16::from_be_bytes(response.get(i..i + 2)?.try_into().ok()?));
+
+    // Check it's our answer and that there was no error (RCODE)
+    if u16_at(0)? != id || u16_at(2)? & 0x000F != 0
+    {
+        return None;
+    }
+    let (questions, answers) = (u16_at(4)?, u16_at(6)?);
+
+    let mut i = 12;
+    for _ in 0..questions
+    {
+        i = skip_name(response, i)? + 4;
+    }
+
+    for _ in 0..answers
+    {
+        i = skip_name(response, i)?;
+        let (kind, len) = (u16_at(i)?, u16_at(i + 8)? as usize);
+        let data = response.get(i + 10..i + 10 + len)?;
+        i += 10 + len;
+
+        if kind == DNS_TYPE_TXT
+        {
+            let text = data.get(1..1 + *data.first()? as usize)?;
+            return Some(String::from_utf8_lossy(text).to_string());
+        }
+    }
+
+    None
+}
+
+/// Skip over a (possibly compressed) name, returning the index after it
+fn skip_name(message: &[u8], mut i: usize) -> Option<usize>
+{
+    loop
+    {
+        let len = *message.get(i)?;
+        match len
+        {
+            0 => return Some(i + 1),
+            // A pointer to a name elsewhere ends this one
+            len if len & 0xC0 == 0xC0 => return Some(i + 2),
+            len => i += len as usize + 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use std::net::TcpListener;
+
+    /// Serve a single HTTP response on a port of `host`, returning the
+    /// endpoint, or `None` if `host` can't be bound (e.g. without IPv6)
+    fn http_stub(host: &str, response: &'static str) -> Option<String>
+    {
+        let listener = TcpListener::bind((host, 0)).ok()?;
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            // Read the whole request so closing the stream doesn't reset it
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n")
+            {
+                let len = stream.read(&mut buf).unwrap();
+                if len == 0
+                {
+                    break;
+                }
+                request.extend_from_slice(&buf[..len]);
+            }
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        Some(format!("http://{addr}/ip"))
+    }
+
+    /// Answer a single DNS query with a TXT record on a port of `host`,
+    /// returning the endpoint, or `None` if `host` can't be bound
+    fn dns_stub(host: &str, txt: &'static str) -> Option<String>
+    {
+        let socket = UdpSocket::bind((host, 0)).ok()?;
+        let addr = socket.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let mut query = [0; 512];
+            let (len, peer) = socket.recv_from(&mut query).unwrap();
+
+            let mut response = query[..len].to_vec();
+            response[2] |= 0x80; // QR
+            response[7] = 1; // ANCOUNT
+            response.extend_from_slice(&[0xC0, 0x0C]); // Name pointer to the question
+            response.extend_from_slice(&DNS_TYPE_TXT.to_be_bytes());
+            response.extend_from_slice(&[0x00, 0x01, 0x00, 0x00, 0x00, 0x3C]);
+            response.extend_from_slice(&(txt.len() as u16 + 1).to_be_bytes());
+            response.push(txt.len() as u8);
+            response.extend_from_slice(txt.as_bytes());
+            socket.send_to(&response, peer).unwrap();
+        });
+        Some(format!("dns://{addr}/o-o.myaddr.l.google.com"))
+    }
+
+    #[test]
+    fn splits_authorities()
+    {
+        assert_eq!(split_authority("example.com"), ("example.com", None));
+        assert_eq!(
+            split_authority("example.com:8080"),
+            ("example.com", Some(8080))
+        );
+        assert_eq!(split_authority("192.0.2.1:53"), ("192.0.2.1", Some(53)));
+        assert_eq!(
+            split_authority("[2001:db8::1]:8053"),
+            ("2001:db8::1", Some(8053))
+        );
+        assert_eq!(split_authority("[2001:db8::1]"), ("2001:db8::1", None));
+        assert_eq!(split_authority("2001:db8::1"), ("2001:db8::1", None));
+    }
+
+    #[test]
+    fn looks_up_from_http()
+    {
+        let response = "HTTP/1.0 200 OK\r\nContent-Type: text/plain\r\n\r\n203.0.113.7\n";
+        // Not every machine has IPv6 loopback
+        for endpoint in ["127.0.0.1", "::1"]
+            .iter()
+            .filter_map(|host| http_stub(host, response))
+        {
+            assert_eq!(
+                lookup(&endpoint).unwrap(),
+                "203.0.113.7".parse::<IpAddr>().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_http_errors()
+    {
+        let endpoint = http_stub(
+            "127.0.0.1",
+            "HTTP/1.0 503 Service Unavailable\r\n\r\n203.0.113.7\n",
+        )
+        .unwrap();
+        assert!(lookup(&endpoint).is_err());
+    }
+
+    #[test]
+    fn looks_up_from_dns()
+    {
+        for endpoint in ["127.0.0.1", "::1"]
+            .iter()
+            .filter_map(|host| dns_stub(host, "2001:db8::7"))
+        {
+            assert_eq!(
+                lookup(&endpoint).unwrap(),
+                "2001:db8::7".parse::<IpAddr>().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn uses_fresh_cache()
+    {
+        let cache = std::env::temp_dir().join(format!("rinfo-wan-{}.toml", std::process::id()));
+        let endpoint = http_stub("127.0.0.1", "HTTP/1.0 200 OK\r\n\r\n203.0.113.7\n").unwrap();
+
+        let first = wan_info(&endpoint, DEFAULT_CACHE_TTL, Some(&cache));
+        // The stub only answers once, so this has to come from the cache
+        let second = wan_info(&endpoint, DEFAULT_CACHE_TTL, Some(&cache));
+        std::fs::remove_file(&cache).unwrap();
+
+        assert_eq!(first.address, "203.0.113.7".parse().ok());
+        assert_eq!(second, first);
+    }
+}
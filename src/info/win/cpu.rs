@@ -25,6 +25,8 @@ pub fn cpu_info() -> Result<Cpu, InfoError>
         cores,
         threads,
         clock_rate,
+        limit: None,
+        temperature: None,
     })
 }
 
@@ -117,3 +117,71 @@ pub fn hostname_info() -> Result<Host, InfoError>
 
     Ok(Host { hostname })
 }
+
+pub fn load_info(_interval: Option<std::time::Duration>) -> Result<Load, InfoError>
+{
+    // TODO: Read the processor queue length and sample `GetSystemTimes`
+    Err(InfoError::Unsupported("The system load".to_string()))
+}
+
+pub fn locale_info() -> Result<Locale, InfoError>
+{
+    // TODO: Use `GetUserDefaultLocaleName` and `GetDynamicTimeZoneInformation`
+    Err(InfoError::Unsupported("The locale".to_string()))
+}
+
+pub fn users_info() -> Result<Users, InfoError>
+{
+    // TODO: Enumerate login sessions
+    Err(InfoError::Unsupported("Logged-in users".to_string()))
+}
+
+pub fn init_info() -> Result<Init, InfoError>
+{
+    Ok(Init {
+        kind: InitKind::Windows,
+        ..Default::default()
+    })
+}
+
+pub fn security_info() -> Result<Security, InfoError>
+{
+    // TODO: Report BitLocker and Secure Boot through WMI
+    Err(InfoError::Unsupported("The security posture".to_string()))
+}
+
+pub fn in_container() -> bool
+{
+    // TODO: Detect Windows containers
+    false
+}
+
+pub fn devices_info() -> Result<Devices, InfoError>
+{
+    // TODO: Enumerate PCI and USB devices
+    Err(InfoError::Unsupported("The device inventory".to_string()))
+}
+
+pub fn audio_info() -> Result<Audio, InfoError>
+{
+    // TODO: List audio endpoints through `IMMDeviceEnumerator`
+    Err(InfoError::Unsupported("Audio devices".to_string()))
+}
+
+pub fn route_info() -> Result<Route, InfoError>
+{
+    // TODO: Read the routing table and DNS configuration
+    Err(InfoError::Unsupported("The routing table".to_string()))
+}
+
+pub fn throughput_info(_interval: std::time::Duration) -> Result<Vec<Throughput>, InfoError>
+{
+    // TODO: Sample interface counters
+    Err(InfoError::Unsupported("Network throughput".to_string()))
+}
+
+pub fn cpu_temperature() -> Option<f64>
+{
+    // TODO: Read the thermal zone from WMI
+    None
+}
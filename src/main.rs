@@ -1,137 +1,272 @@
 #![allow(non_camel_case_types)]
 
-use serde::{Deserialize, Serialize};
 use structopt::StructOpt;
+mod cli;
+mod config;
 mod info;
+mod modules;
 mod printing;
+use cli::{Cli, Command, ConfigCommand};
+use config::Config;
 use info::*;
+use modules::MODULES;
+use printing::redact::Redactor;
 use std::io::Write;
 
-/// Conditionally append the result of a function to the string to be later
-/// printed.
-macro_rules! add_info {
-    ($vec:expr, $cond:expr, $func:expr) => {
-        if $cond
+/// Render the output template, reading only the modules it uses
+fn render_template(
+    source: &str,
+    config: &Config,
+    os: &OperatingSystem,
+    redactor: Option<&Redactor>,
+) -> String
+{
+    use printing::template::Template;
+
+    let rendered = Template::parse(source).and_then(|template| {
+        let names: Vec<&str> = MODULES.iter().map(|module| module.name).collect();
+        template.check(&names)?;
+        let modules: Vec<(&str, Value)> = MODULES
+            .iter()
+            .filter(|module| template.uses(&[module.name]))
+            .map(|module| {
+                let mut info = InfoError::report_supported((module.read)(config, os));
+                if let (Some(redactor), Some(info)) = (redactor, info.as_mut())
+                {
+                    redactor.redact_fields(module.name, info.as_mut());
+                }
+                (
+                    module.name,
+                    info.map_or(Value::None, |info| Value::Map(info.fields())),
+                )
+            })
+            .collect();
+        template.render(&Value::Map(modules))
+    });
+
+    match rendered
+    {
+        Ok(info) => info,
+        Err(e) =>
         {
-            let result = $func();
-            let info = InfoError::report(result);
-            write!($vec, "\n{}", info).unwrap();
+            eprintln!("Couldn't use the output template: {e}");
+            std::process::exit(65);
         }
-    };
+    }
 }
 
-#[derive(
-    Debug, StructOpt, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default,
-)]
-#[structopt(name = "rinfo", about = "Get information about your system")]
-#[serde(rename_all = "camelCase")]
-struct Config
+/// Run a `config` subcommand
+fn config_command(command: ConfigCommand, cli: Cli)
 {
-    /// Don't print CPU information
-    #[structopt(short = "c", long)]
-    omit_cpu: bool,
-
-    /// Don't print RAM information
-    #[structopt(short = "r", long)]
-    omit_ram: bool,
-
-    /// Don't print motherboard information
-    #[structopt(short = "m", long)]
-    omit_motherboard: bool,
+    use config::check;
 
-    /// Don't print caller (USER, SHELL) information
-    #[structopt(short = "p", long)]
-    omit_caller: bool,
-
-    /// Don't print the system hostname
-    #[structopt(short = "n", long)]
-    omit_hostname: bool,
-
-    /// Don't print operating system information
-    #[structopt(short = "o", long)]
-    omit_os: bool,
-
-    /// Don't print character art
-    #[structopt(short = "a", long)]
-    omit_art: bool,
-
-    /// Don't print local IP address
-    #[structopt(short = "i", long)]
-    omit_ip: bool,
-
-    /// Print character art above information
-    #[structopt(short = "v", long)]
-    vertical_art: bool,
-}
+    let path = || {
+        match Config::path(cli.config.clone(), cli.no_config)
+        {
+            Some((path, _)) => path,
+            None =>
+            {
+                eprintln!("No config file is read with --no-config");
+                std::process::exit(64);
+            }
+        }
+    };
 
-impl Config
-{
-    /// Combine mutliple configs in a way that prioritizes the `other`'s true
-    /// fields over `self`'s false ones.
-    pub fn combine(&mut self, other: Self)
+    match command
     {
-        self.omit_art |= !self.omit_art && other.omit_art;
-        self.omit_caller |= !self.omit_caller && other.omit_caller;
-        self.omit_cpu |= !self.omit_cpu && other.omit_cpu;
-        self.omit_hostname |= !self.omit_hostname && other.omit_hostname;
-        self.omit_motherboard |= !self.omit_motherboard && other.omit_motherboard;
-        self.omit_os |= !self.omit_os && other.omit_os;
-        self.vertical_art |= !self.vertical_art && other.vertical_art;
-        self.omit_ip |= !self.omit_ip && other.omit_ip;
+        ConfigCommand::Init { force } =>
+        {
+            let path = path();
+            if path.exists() && !force
+            {
+                eprintln!(
+                    "'{}' already exists; use --force to replace it",
+                    path.display()
+                );
+                std::process::exit(73);
+            }
+            let written = path
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|_| std::fs::write(&path, check::DEFAULT));
+            if let Err(e) = written
+            {
+                eprintln!("Couldn't write config file '{}': {e}", path.display());
+                std::process::exit(73);
+            }
+            println!("Wrote {}", path.display());
+        }
+        ConfigCommand::Check =>
+        {
+            let path = path();
+            let source = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+                eprintln!("Couldn't read config file '{}': {e}", path.display());
+                std::process::exit(66);
+            });
+            let diagnostics = check::check(&source);
+            for diagnostic in &diagnostics
+            {
+                println!("{}:{diagnostic}", path.display());
+            }
+            if !diagnostics.is_empty()
+            {
+                std::process::exit(65);
+            }
+            println!("{}: no problems found", path.display());
+        }
+        ConfigCommand::Path => println!("{}", path().display()),
+        ConfigCommand::Show =>
+        {
+            let config = Config::load(cli.config, cli.no_config, cli.profile, cli.flags.into());
+            match config.to_toml()
+            {
+                Ok(config) => print!("{config}"),
+                Err(e) =>
+                {
+                    eprintln!("Couldn't print the configuration: {e}");
+                    std::process::exit(70);
+                }
+            }
+        }
     }
 }
 
 fn main()
 {
-    // Load configuration
-    let mut config = Config::default();
-    if let Some(config_dir) = dirs::config_dir()
+    let mut cli = Cli::from_args();
+
+    if let Some(command) = cli.command.take()
     {
-        let config_file = config_dir.join("SBII").join("rinfo.toml");
-        if let Ok(contents) = std::fs::read_to_string(config_file)
+        match command
         {
-            match toml::from_str(&contents)
+            Command::Devices { class } =>
             {
-                Ok(config_from_file) =>
+                let mut devices = InfoError::report(Devices::read());
+                if let Some(class) = class
                 {
-                    config.combine(config_from_file);
-                }
-                Err(e) =>
-                {
-                    eprintln!("Couldn't parse config file: {e:?}");
+                    devices.filter_class(&class);
                 }
+                println!("{devices}");
             }
+            Command::Config(command) => config_command(command, cli),
         }
+        return;
     }
 
-    config.combine(Config::from_args());
-
+    // Load configuration
+    let config = Config::load(cli.config, cli.no_config, cli.profile, cli.flags.into());
 
     // Build information string
-    let mut info_vec = Vec::new();
     let os = InfoError::report(OperatingSystem::read());
+    let redactor = config
+        .redact
+        .unwrap_or_default()
+        .then(|| Redactor::load(config.section("redact")));
+    let mut info_str = match &config.template
+    {
+        Some(template) => render_template(template, &config, &os, redactor.as_ref()),
+        None =>
+        {
+            let mut info_vec = Vec::new();
+            for module in modules::selected(&config)
+            {
+                let Some(mut info) = InfoError::report_supported((module.read)(&config, &os))
+                else
+                {
+                    continue;
+                };
+                if let Some(redactor) = &redactor
+                {
+                    redactor.redact_fields(module.name, info.as_mut());
+                }
+                write!(info_vec, "\n{info}").unwrap();
+            }
 
-    add_info!(info_vec, !config.omit_cpu, &Cpu::read);
-    add_info!(info_vec, !config.omit_ram, &Memory::read);
-    add_info!(info_vec, !config.omit_motherboard, &BaseBoard::read);
-    add_info!(info_vec, !config.omit_ip, &Net::read);
-    add_info!(info_vec, !config.omit_hostname, &Host::read);
-    add_info!(info_vec, !config.omit_caller, &Caller::read);
-    add_info!(info_vec, !config.omit_os, &|| Ok(os.clone()));
+            String::from_utf8_lossy(&info_vec).trim_start().to_string() // We `trim_start()` to trim the leading newline
+        }
+    };
+    if let Some(redactor) = &redactor
+    {
+        info_str = redactor.redact(&info_str);
+    }
+
+    // Choose the art and its colors. Custom art uses the built-in logo's colors
+    // unless the theme says otherwise.
+    let logo = config.logo.unwrap_or(os.art);
+    let art = match &config.logo_file
+    {
+        Some(path) =>
+        {
+            printing::Art::from_file(path).unwrap_or_else(|e| {
+                eprintln!("Couldn't read logo file '{}': {e}", path.display());
+                printing::Art::Builtin(logo)
+            })
+        }
+        None => printing::Art::Builtin(logo),
+    };
+    let palette = config
+        .color
+        .unwrap_or_default()
+        .enabled()
+        .then(|| config.theme.palette(logo.logo().colors));
+    if let Some(label) = palette
+        .as_deref()
+        .and_then(|palette| config.theme.label_color(palette))
+    {
+        info_str = printing::color_labels(&info_str, label);
+    }
 
-    let info_str = String::from_utf8_lossy(&info_vec).trim_start().to_string(); // We `trim_start()` to trim the leading newline
+    // Draw the logo as an image if asked to and it fits, otherwise fall back to
+    // the character art
+    let width = config.width.or_else(printing::layout::terminal_width);
+    let omit_art = config.omit_art.unwrap_or_default();
+    let small_art = config.small_art.unwrap_or_default();
+    let vertical_art = config.vertical_art.unwrap_or_default();
+    if config.image.unwrap_or_default() && !omit_art && !vertical_art
+    {
+        let image = printing::image::ImageLogo::find(
+            config.image_file.as_deref(),
+            config.image_protocol,
+            config.image_width.unwrap_or_else(|| art.width(small_art)),
+        );
+        if let Some(image) = image.filter(|image| image.fits(&info_str, width))
+        {
+            print!("{}", image.with(&info_str));
+            return;
+        }
+    }
+
+    // Fit the output to the terminal
+    let mut layout = printing::layout::Layout {
+        small: small_art,
+        vertical: vertical_art,
+        omit_art,
+    };
+    if let Some(width) = width
+    {
+        info_str = layout.fit(&art, &info_str, width);
+    }
 
     // Print information
-    if config.omit_art
+    if layout.omit_art
     {
         println!("{info_str}");
     }
-    else if config.vertical_art
+    else if layout.vertical
     {
-        println!("{}\n{info_str}", os.art);
+        println!(
+            "{}\n{info_str}",
+            art.paint(layout.small, palette.as_deref())
+        );
     }
     else
     {
-        printing::print_with_logo(os.art, &info_str);
+        printing::print_with_logo(
+            &art,
+            layout.small,
+            palette.as_deref(),
+            config.center.unwrap_or_default(),
+            &info_str,
+        );
     }
 }
@@ -0,0 +1,455 @@
+//! The modules of information that can be printed, and the options they read
+//! from their `[module]` tables.
+//!
+//! Adding a module is adding an entry to `MODULES`; its options are
+//! deserialized from `Config::section`, so nothing else needs to know about
+//! them. Only a module with its own `--x`/`--no-x` flags needs an `omit_*`
+//! option in `Config`, its flags in `cli::Flags`, and an entry in
+//! `default.toml`; `modules` lists can name any module without them.
+
+use crate::{
+    config::Config,
+    info::*,
+    printing::template::{suggest, Template},
+};
+use humansize::{FormatSizeOptions, BINARY, DECIMAL};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::time::Duration;
+
+/// Information a module read, to be printed or referenced from a template
+pub trait Output: std::fmt::Display + Fields {}
+
+impl<T: std::fmt::Display + Fields> Output for T {}
+
+/// The result of reading a module
+pub type Reading = Result<Box<dyn Output>, InfoError>;
+
+/// A module of information
+pub struct Module
+{
+    /// The module's name in `modules` lists, tables, and templates
+    pub name: &'static str,
+
+    /// Is the module printed when `modules` isn't configured?
+    pub default: bool,
+
+    /// Has the module been turned on (`Some(true)`) or off by a flag?
+    pub enabled: fn(&Config) -> Option<bool>,
+
+    /// Read the module's information
+    pub read: fn(&Config, &OperatingSystem) -> Reading,
+
+    /// Check that a `[name]` table fits the module's options
+    pub options: fn(toml::Table) -> Result<(), toml::de::Error>,
+}
+
+/// Every module, in the default order
+pub const MODULES: [Module; 15] = [
+    Module {
+        name: "cpu",
+        default: true,
+        enabled: |config| config.omit_cpu.map(|omit| !omit),
+        read: read_cpu,
+        options: options::<CpuOptions>,
+    },
+    Module {
+        name: "memory",
+        default: true,
+        enabled: |config| config.omit_ram.map(|omit| !omit),
+        read: read_memory,
+        options: options::<MemoryOptions>,
+    },
+    Module {
+        name: "load",
+        default: false,
+        enabled: |config| config.omit_load.map(|omit| !omit),
+        read: read_load,
+        options: options::<LoadOptions>,
+    },
+    Module {
+        name: "board",
+        default: true,
+        enabled: |config| config.omit_motherboard.map(|omit| !omit),
+        read: |_, _| boxed(BaseBoard::read()),
+        options: options::<NoOptions>,
+    },
+    Module {
+        name: "audio",
+        default: false,
+        enabled: |config| config.omit_audio.map(|omit| !omit),
+        read: |_, _| boxed(Audio::read()),
+        options: options::<NoOptions>,
+    },
+    Module {
+        name: "net",
+        default: true,
+        enabled: |config| config.omit_ip.map(|omit| !omit),
+        read: read_net,
+        options: options::<NetOptions>,
+    },
+    Module {
+        name: "wan",
+        default: false,
+        enabled: |config| config.wan,
+        read: read_wan,
+        options: options::<WanOptions>,
+    },
+    Module {
+        name: "route",
+        default: false,
+        enabled: |config| config.omit_route.map(|omit| !omit),
+        read: |_, _| boxed(Route::read()),
+        options: options::<NoOptions>,
+    },
+    Module {
+        name: "host",
+        default: true,
+        enabled: |config| config.omit_hostname.map(|omit| !omit),
+        read: |_, _| boxed(Host::read()),
+        options: options::<NoOptions>,
+    },
+    Module {
+        name: "caller",
+        default: true,
+        enabled: |config| config.omit_caller.map(|omit| !omit),
+        read: |_, _| boxed(Caller::read()),
+        options: options::<NoOptions>,
+    },
+    Module {
+        name: "users",
+        default: false,
+        enabled: |config| config.omit_users.map(|omit| !omit),
+        read: |_, _| boxed(Users::read()),
+        options: options::<NoOptions>,
+    },
+    Module {
+        name: "os",
+        default: true,
+        enabled: |config| config.omit_os.map(|omit| !omit),
+        read: |_, os| boxed(Ok(os.clone())),
+        options: options::<NoOptions>,
+    },
+    Module {
+        name: "init",
+        default: false,
+        enabled: |config| config.omit_init.map(|omit| !omit),
+        read: |_, _| boxed(Init::read()),
+        options: options::<NoOptions>,
+    },
+    Module {
+        name: "security",
+        default: false,
+        enabled: |config| config.omit_security.map(|omit| !omit),
+        read: |_, _| boxed(Security::read()),
+        options: options::<NoOptions>,
+    },
+    Module {
+        name: "locale",
+        default: false,
+        enabled: |config| config.omit_locale.map(|omit| !omit),
+        read: |_, _| boxed(Locale::read()),
+        options: options::<NoOptions>,
+    },
+];
+
+/// The module called `name`
+pub fn find(name: &str) -> Option<&'static Module>
+{
+    MODULES.iter().find(|module| module.name == name)
+}
+
+/// The modules to print, in order: those in the `modules` list (or the
+/// default ones) that haven't been turned off, then any others that have been
+/// turned on
+pub fn selected(config: &Config) -> Vec<&'static Module>
+{
+    let mut selected: Vec<&'static Module> = Vec::new();
+    let listed: Vec<&'static Module> = match &config.modules
+    {
+        Some(names) =>
+        {
+            names
+                .iter()
+                .filter_map(|name| {
+                    let module = find(name);
+                    if module.is_none()
+                    {
+                        let names = MODULES.iter().map(|module| module.name);
+                        match suggest(name, names)
+                        {
+                            Some(suggestion) =>
+                            {
+                                eprintln!("Unknown module '{name}'; did you mean '{suggestion}'?")
+                            }
+                            None => eprintln!("Unknown module '{name}'"),
+                        }
+                    }
+                    module
+                })
+                .collect()
+        }
+        None => MODULES.iter().filter(|module| module.default).collect(),
+    };
+
+    let turned_on = MODULES
+        .iter()
+        .filter(|module| (module.enabled)(config) == Some(true));
+    for module in listed.into_iter().chain(turned_on)
+    {
+        if (module.enabled)(config) != Some(false)
+            && !selected.iter().any(|selected| selected.name == module.name)
+        {
+            selected.push(module);
+        }
+    }
+    selected
+}
+
+/// The table of a module without options
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NoOptions {}
+
+/// Check that `table` fits the options `T`
+fn options<T: DeserializeOwned>(table: toml::Table) -> Result<(), toml::de::Error>
+{
+    toml::Value::Table(table).try_into::<T>().map(drop)
+}
+
+fn boxed<T: Output + 'static>(info: Result<T, InfoError>) -> Reading { Ok(Box::new(info?)) }
+
+/// Information, and the options to print it with
+struct Configured<T, O>
+{
+    info: T,
+    options: O,
+}
+
+impl<T: Fields, O> Fields for Configured<T, O>
+{
+    fn fields(&self) -> Vec<(&'static str, Value)> { self.info.fields() }
+
+    fn text_fields_mut(&mut self) -> Vec<(&'static str, &mut String)>
+    {
+        self.info.text_fields_mut()
+    }
+}
+
+/// The `[cpu]` table
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Deserialize)]
+#[serde(default)]
+struct CpuOptions
+{
+    /// Print the CPU's temperature
+    show_temp: bool,
+}
+
+fn read_cpu(config: &Config, _: &OperatingSystem) -> Reading
+{
+    let options = config.section::<CpuOptions>("cpu");
+    // A template can print the temperature without `show_temp`
+    let templated = matches!(
+        config.template.as_deref().map(Template::parse),
+        Some(Ok(template)) if template.uses(&["cpu", "temperature"])
+    );
+    Ok(Box::new(Configured {
+        info: Cpu::read_with_temperature(options.show_temp || templated)?,
+        options,
+    }))
+}
+
+impl std::fmt::Display for Configured<Cpu, CpuOptions>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let mut cpu = self.info.clone();
+        if !self.options.show_temp
+        {
+            cpu.temperature = None;
+        }
+        write!(f, "{cpu}")
+    }
+}
+
+/// How sizes are printed
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SizeUnit
+{
+    /// Powers of 1024 (KiB, MiB, GiB)
+    #[default]
+    Binary,
+
+    /// Powers of 1000 (kB, MB, GB)
+    Si,
+}
+
+impl SizeUnit
+{
+    fn format(self) -> FormatSizeOptions
+    {
+        match self
+        {
+            Self::Binary => BINARY,
+            Self::Si => DECIMAL,
+        }
+    }
+}
+
+/// The `[memory]` table
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Deserialize)]
+#[serde(default)]
+struct MemoryOptions
+{
+    unit: SizeUnit,
+}
+
+fn read_memory(config: &Config, _: &OperatingSystem) -> Reading
+{
+    Ok(Box::new(Configured {
+        info: Memory::read()?,
+        options: config.section::<MemoryOptions>("memory"),
+    }))
+}
+
+impl std::fmt::Display for Configured<Memory, MemoryOptions>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        self.info.fmt_with(f, self.options.unit.format())
+    }
+}
+
+/// The `[load]` table
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Deserialize)]
+#[serde(default)]
+struct LoadOptions
+{
+    /// Sample and print CPU utilisation, which blocks for the interval
+    usage: bool,
+
+    /// Milliseconds to sample CPU utilisation over (setting it turns on
+    /// `usage`)
+    interval: Option<u64>,
+}
+
+fn read_load(config: &Config, _: &OperatingSystem) -> Reading
+{
+    let options: LoadOptions = config.section("load");
+    boxed(Load::sample(
+        (options.usage || options.interval.is_some()).then(|| {
+            options
+                .interval
+                .map_or(Load::DEFAULT_INTERVAL, Duration::from_millis)
+        }),
+    ))
+}
+
+/// The `[net]` table
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Deserialize)]
+#[serde(default)]
+struct NetOptions
+{
+    /// Sample and print the throughput of each network interface
+    rate: bool,
+
+    /// Milliseconds to sample network throughput over
+    rate_interval: Option<u64>,
+}
+
+fn read_net(config: &Config, _: &OperatingSystem) -> Reading
+{
+    let options: NetOptions = config.section("net");
+    boxed(Net::sample(options.rate.then(|| {
+        options
+            .rate_interval
+            .map_or(Net::DEFAULT_RATE_INTERVAL, Duration::from_millis)
+    })))
+}
+
+/// The `[wan]` table
+#[derive(Debug, PartialEq, Eq, Clone, Default, Deserialize)]
+#[serde(default)]
+struct WanOptions
+{
+    /// The endpoint to look up the public IP address from
+    endpoint: Option<String>,
+
+    /// Seconds to reuse a looked up public IP address for
+    cache_ttl: Option<u64>,
+}
+
+fn read_wan(config: &Config, _: &OperatingSystem) -> Reading
+{
+    let options: WanOptions = config.section("wan");
+    boxed(Ok(Wan::lookup(
+        options.endpoint.as_deref().unwrap_or(wan::DEFAULT_ENDPOINT),
+        options
+            .cache_ttl
+            .map_or(wan::DEFAULT_CACHE_TTL, Duration::from_secs),
+    )))
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn names(config: &Config) -> Vec<&'static str>
+    {
+        selected(config).iter().map(|module| module.name).collect()
+    }
+
+    #[test]
+    fn selects_modules_in_order()
+    {
+        let mut config = Config::default();
+        assert_eq!(
+            names(&config),
+            ["cpu", "memory", "board", "net", "host", "caller", "os"]
+        );
+
+        config.modules = Some(vec!["os".into(), "cpu".into(), "wan".into(), "cpu".into()]);
+        assert_eq!(names(&config), ["os", "cpu", "wan"]);
+
+        config.omit_cpu = Some(true);
+        config.omit_hostname = Some(true);
+        assert_eq!(names(&config), ["os", "wan"]);
+
+        config.modules = Some(vec!["host".into(), "memory".into()]);
+        config.wan = Some(true);
+        config.omit_os = Some(false);
+        assert_eq!(names(&config), ["memory", "wan", "os"]);
+    }
+
+    #[test]
+    fn reads_module_tables()
+    {
+        let mut config: Config = toml::from_str(concat!(
+            "omitArt = true\n",
+            "modules = [\"memory\", \"cpu\"]\n",
+            "[memory]\n",
+            "unit = \"si\"\n",
+            "[cpu]\n",
+            "show_temp = true\n",
+        ))
+        .unwrap();
+        assert_eq!(config.omit_art, Some(true));
+        assert_eq!(
+            config.modules.as_deref(),
+            Some(&["memory".into(), "cpu".into()][..])
+        );
+        assert_eq!(
+            config.section::<MemoryOptions>("memory"),
+            MemoryOptions { unit: SizeUnit::Si }
+        );
+
+        config.combine(toml::from_str("[cpu]\nshow_temp = false").unwrap());
+        assert_eq!(config.section::<MemoryOptions>("memory").unit, SizeUnit::Si);
+        assert_eq!(config.section::<CpuOptions>("cpu"), CpuOptions::default());
+        assert_eq!(
+            config.section::<LoadOptions>("load"),
+            LoadOptions::default()
+        );
+    }
+}
@@ -1,4 +1,4 @@
-pub const ARCH_LINUX: &str = r#"                   -`
+pub const ARCH_LINUX: &str = r#"${c1}                   -`
                   .o+`
                  `ooo/
                 `+oooo:
@@ -8,7 +8,7 @@ pub const ARCH_LINUX: &str = r#"                   -`
             `/++++/+++++++:
            `/++++++++++++++:
           `/+++ooooooooooooo/`
-         ./ooosssso++osssssso+`
+${c2}         ./ooosssso++osssssso+`
         .oossssso-````/ossssss+`
        -osssssso.      :ssssssso.
       :osssssss/        osssso+++.
@@ -18,7 +18,7 @@ pub const ARCH_LINUX: &str = r#"                   -`
  `++:.                           `-/+\
  .`                                 ` ."#;
 
-pub const ALPINE_LINUX: &str = r#"       .hddddddddddddddddddddddh.
+pub const ALPINE_LINUX: &str = r#"${c1}       .hddddddddddddddddddddddh.
       :dddddddddddddddddddddddddd:
      /dddddddddddddddddddddddddddd/
     +dddddddddddddddddddddddddddddd+
@@ -39,7 +39,7 @@ hdddyo+ohddyosdddddddddho+oydddy++ohdddh
       :dddddddddddddddddddddddddd:
        .hddddddddddddddddddddddh."#;
 
-pub const DEBIAN: &str = r#"          _,met$$$$$gg.
+pub const DEBIAN: &str = r#"${c1}          _,met$$$$$gg.
     ,g$$$$$$$$$$$$$$$P.
   ,g$$P"     """Y$$.".
  ,$$P'              `$$$.
@@ -57,7 +57,7 @@ pub const DEBIAN: &str = r#"          _,met$$$$$gg.
           `"Y$b._
               `""""#;
 
-pub const WINDOWS_10_11: &str = r#"################  ################
+pub const WINDOWS_10_11: &str = r#"${c1}################  ################
 ################  ################
 ################  ################
 ################  ################
@@ -73,7 +73,7 @@ pub const WINDOWS_10_11: &str = r#"################  ################
 ################  ################
 ################  ################"#;
 
-pub const WINDOWS: &str = r#"                    ....,,:;+ccllll
+pub const WINDOWS: &str = r#"${c1}                    ....,,:;+ccllll
       ...,,+:;  cllllllllllllllllll
 ,cclllllllllll  lllllllllllllllllll
 llllllllllllll  lllllllllllllllllll
@@ -92,25 +92,25 @@ llllllllllllll  lllllllllllllllllll
                        ````''*::cll
                                  ``"#;
 
-pub const MACOS: &str = r#"                    c.'
+pub const MACOS: &str = r#"${c1}                    c.'
                  ,xNMM.
                .OMMMMo
                lMM"
-     .;loddo:.  .olloddol;.
+${c2}     .;loddo:.  .olloddol;.
    cKMMMMMMMMMMNWMMMMMMMMMM0:
- .KMMMMMMMMMMMMMMMMMMMMMMMWd.
+${c3} .KMMMMMMMMMMMMMMMMMMMMMMMWd.
  XMMMMMMMMMMMMMMMMMMMMMMMX.
 ;MMMMMMMMMMMMMMMMMMMMMMMM:
-:MMMMMMMMMMMMMMMMMMMMMMMM:
+${c4}:MMMMMMMMMMMMMMMMMMMMMMMM:
 .MMMMMMMMMMMMMMMMMMMMMMMMX.
- kMMMMMMMMMMMMMMMMMMMMMMMMWd.
+${c5} kMMMMMMMMMMMMMMMMMMMMMMMMWd.
  'XMMMMMMMMMMMMMMMMMMMMMMMMMMk
   'XMMMMMMMMMMMMMMMMMMMMMMMMK.
-    kMMMMMMMMMMMMMMMMMMMMMMd
+${c6}    kMMMMMMMMMMMMMMMMMMMMMMd
      ;KMMMMMMMWXXWMMMMMMMk.
        "cooc*"    "*coo'""#;
 
-pub const UNKNOWN: &str = r#"           @@@%%%%%%%%%@@
+pub const UNKNOWN: &str = r#"${c1}           @@@%%%%%%%%%@@
         @@@%%%%%%%%%#######%@@
       @@@@%%%%%%%######?######%@
      @@@@%%%%%%%#######:########%@
@@ -127,3 +127,526 @@ pub const UNKNOWN: &str = r#"           @@@%%%%%%%%%@@
  @???;;?+;;;+ ;:;;......;;;#@
  %##?++?+++;+ ??% @%%@@@@
  @_:?_:+_:_:#%"#;
+
+pub const UBUNTU: &str = r#"${c1}            .-/+oossssoo+/-.
+        `:+ssssssssssssssssss+:`
+      -+ssssssssssssssssssyyssss+-
+    .ossssssssssssssssss${c2}dMMMNy${c1}sssso.
+   /sssssssssss${c2}hdmmNNmmyNMMMMh${c1}ssssss/
+  +sssssssss${c2}hm${c1}yd${c2}MMMMMMMNddddy${c1}ssssssss+
+ /ssssssss${c2}hNMMM${c1}yh${c2}hyyyyhmNMMMNh${c1}ssssssss/
+.ssssssss${c2}dMMMNh${c1}ssssssssss${c2}hNMMMd${c1}ssssssss.
++ssss${c2}hhhyNMMNy${c1}ssssssssssss${c2}yNMMMy${c1}sssssss+
+oss${c2}yNMMMNyMMh${c1}ssssssssssssss${c2}hmmmh${c1}ssssssso
+oss${c2}yNMMMNyMMh${c1}ssssssssssssss${c2}hmmmh${c1}ssssssso
++ssss${c2}hhhyNMMNy${c1}ssssssssssss${c2}yNMMMy${c1}sssssss+
+.ssssssss${c2}dMMMNh${c1}ssssssssss${c2}hNMMMd${c1}ssssssss.
+ /ssssssss${c2}hNMMM${c1}yh${c2}hyyyyhdNMMMNh${c1}ssssssss/
+  +sssssssss${c2}dm${c1}yd${c2}MMMMMMMMddddy${c1}ssssssss+
+   /sssssssssss${c2}hdmNNNNmyNMMMMh${c1}ssssss/
+    .ossssssssssssssssss${c2}dMMMNy${c1}sssso.
+      -+sssssssssssssssss${c2}yyy${c1}ssss+-
+        `:+ssssssssssssssssss+:`
+            .-/+oossssoo+/-."#;
+
+pub const FEDORA: &str = r#"${c1}             .',;::::;,'.
+         .';:cccccccccccc:;,.
+      .;cccccccccccccccccccccc;.
+    .:cccccccccccccccccccccccccc:.
+  .;ccccccccccccc;${c2}.:dddl:.${c1};ccccccc;.
+ .:ccccccccccccc;${c2}OWMKOOXMWd${c1};ccccccc:.
+.:ccccccccccccc;${c2}KMMc${c1};cc;${c2}xMMc${c1}:ccccccc:.
+,cccccccccccccc;${c2}MMM.${c1};cc;${c2};WW:${c1}:cccccccc,
+:cccccccccccccc;${c2}MMM.${c1};cccccccccccccccc:
+:ccccccc;${c2}oxOOOo${c1};${c2}MMM0OOk.${c1};cccccccccccc:
+cccccc:${c2}0MMKxdd:${c1};${c2}MMMkddc.${c1};cccccccccccc;
+ccccc:${c2}XM0'${c1};cccc;${c2}MMM.${c1};cccccccccccccccc'
+ccccc;${c2}MMo${c1};ccccc;${c2}MMW.${c1};ccccccccccccccc;
+ccccc;${c2}0MNc.${c1}ccc${c2}.xMMd${c1}:ccccccccccccccc;
+cccccc;${c2}dNMWXXXWM0:${c1}:cccccccccccccc:,
+cccccccc;${c2}.:odl:.${c1};cccccccccccccc:,.
+:cccccccccccccccccccccccccccc:'.
+.:cccccccccccccccccccccc:;,..
+  '::cccccccccccccc::;,."#;
+
+pub const OPENSUSE: &str = r#"${c2}           .;ldkO0000Okdl;.
+       .;d00xl:^''''''^:ok00d;.
+     .d00l'                'o00d.
+   .d0Kd'${c1}  Okxol:;,.          ${c2}:O0d.
+  .OK${c1}KKK0kOKKKKKKKKKKOxo:,      ${c2}lKO.
+ ,0K${c1}KKKKKKKKKKKKKKK0P^${c2},,,${c1}^dx:${c2}    ;00,
+.OK${c1}KKKKKKKKKKKKKKKk'${c2}.oOPPb.${c1}'0k.${c2}   cKO.
+:KK${c1}KKKKKKKKKKKKKKK: ${c2}kKx..dd ${c1}lKd${c2}   'OK:
+dKK${c1}KKKKKKKKKOx0KKKd ${c2}^0KKKO' ${c1}kKKc${c2}   dKd
+dKK${c1}KKKKKKKKKK;.;oOKx,..${c2}^${c1}..;kKKK0.${c2}  dKd
+:KK${c1}KKKKKKKKKK0o;...^cdxxOK0O/^^'  ${c2}.0K:
+ kKK${c1}KKKKKKKKKKKKK0x;,,......,;od  ${c2}lKk
+ '0K${c1}KKKKKKKKKKKKKKKKKKKK00KKOo^  ${c2}c00'
+  'kKK${c1}KOxddxkOO00000Okxoc;''   ${c2}.dKk'
+    l0Ko.                    .c00l'
+     'l0Kk:.              .;xK0l'
+        'lkK0xl:;,,,,;:ldO0kl'
+            '^:ldxkkkkxdl:^'"#;
+
+pub const NIXOS: &str = r#"${c1}          ::::.    ${c2}':::::     ::::'
+${c1}          ':::::    ${c2}':::::.  ::::'
+${c1}            :::::     ${c2}'::::.:::::
+${c1}      .......:::::..... ${c2}::::::::
+${c1}     ::::::::::::::::::. ${c2}::::::    ${c1}::::.
+    ::::::::::::::::::::: ${c2}:::::.  ${c1}.::::'
+${c2}           .....           ::::' ${c1}:::::'
+${c2}          :::::            '::' ${c1}:::::'
+${c2} ........:::::               ' ${c1}:::::::::::.
+${c2}:::::::::::::                 ${c1}:::::::::::::
+${c2} ::::::::::: ${c1}..              :::::
+${c2}     .::::: ${c1}.:::            :::::
+${c2}    .:::::  ${c1}:::::          '''''    ${c2}.....
+    :::::   ${c1}':::::.  ${c2}......:::::::::::::'
+     :::     ${c1}::::::. ${c2}':::::::::::::::::'
+${c1}            .:::::::: ${c2}'::::::::::
+${c1}           .::::''::::.     ${c2}'::::.
+${c1}          .::::'   ::::.     ${c2}'::::.
+${c1}         .::::      ::::      ${c2}'::::."#;
+
+pub const GENTOO: &str = r#"${c1}         -/oyddmdhs+:.
+     -o${c2}dNMMMMMMMMNNmhy+${c1}-`
+   -y${c2}NMMMMMMMMMMMNNNmmdhy${c1}+-
+ `o${c2}mMMMMMMMMMMMMNmdmmmmddhhy${c1}/`
+ om${c2}MMMMMMMMMMMN${c1}hhyyyo${c2}hmdddhhhd${c1}o`
+.y${c2}dMMMMMMMMMMd${c1}hs++so/s${c2}mdddhhhhdm${c1}+`
+ oy${c2}hdmNMMMMMMMN${c1}dyooy${c2}dmddddhhhhyhN${c1}d.
+  :o${c2}yhhdNNMMMMMMMNNNmmdddhhhhhyym${c1}Mh
+    .:${c2}+sydNMMMMMNNNmmmdddhhhhhhmM${c1}my
+       /m${c2}MMMMMMNNNmmmdddhhhhhmMNh${c1}s:
+    `o${c2}NMMMMMMMNNNmmmddddhhdmMNhs${c1}+`
+  `s${c2}NMMMMMMMMNNNmmmdddddmNMmhs${c1}/.
+ /N${c2}MMMMMMMMNNNNmmmdddmNMNdso${c1}:`
++M${c2}MMMMMMNNNNNmmmmdmNMNdso${c1}/-
+yM${c2}MNNNNNNNmmmmmNNMmhs+/${c1}-`
+/h${c2}MMNNNNNNNNMNdhs++/${c1}-`
+`/${c2}ohdmmddhys+++/:${c1}.`
+  `-//////:--."#;
+
+pub const VOID: &str = r#"${c1}                __.;=====;.__
+            _.=+==++=++=+=+===;.
+             -=+++=+===+=+=+++++=_
+        .     -=:``     `--==+=++==.
+       _vi,    `            --+=++++:
+      .uvnvi.       _._       -==+==+.
+     .vvnvnI`    .;==|==;.     :|=||=|.
+${c2}+QmQQm${c1}pvvnv; ${c2}_yYsyQQWUUQQQm #QmQ#${c1}:${c2}QQQWUV$QQm.
+${c2} -QQWQW${c1}pvvo${c2}wZ?.wQQQE${c1}==<${c2}QWWQ/QWQW.QQWW${c1}(: ${c2}jQWQE
+${c2}  -$QQQQmmU'  jQQQ@${c1}+=<${c2}QWQQ)mQQQ.mQQQC${c1}+;${c2}jWQQ@'
+${c2}   -$WQ8Y${c1}nI:   ${c2}QWQQwgQQWV${c1}`${c2}mWQQ.jQWQQgyyWW@!
+${c1}     -1vvnvv.     `~+++`        ++|+++
+      +vnvnnv,                 `-|===
+       +vnvnvns.           .      :=-
+        -Invnvvnsi..___..=sv=.     `
+          +Invnvnvnnnnnnnnvvnn;.
+            ~|Invnvnvvnvvvnnv}+`
+               -~|{*l}*|~"#;
+
+pub const MANJARO: &str = r#"${c1}##################  ########
+##################  ########
+##################  ########
+##################  ########
+########            ########
+########  ########  ########
+########  ########  ########
+########  ########  ########
+########  ########  ########
+########  ########  ########
+########  ########  ########
+########  ########  ########
+########  ########  ########
+########  ########  ########"#;
+
+pub const LINUX_MINT: &str = r#"${c1}MMMMMMMMMMMMMMMMMMMMMMMMMmds+.
+MMm----::-://////////////oymNMd+`
+MMd      ${c2}/++                ${c1}-sNMd:
+MMNso/`  ${c2}dMM    `.::-. .-::.` ${c1}.hMN:
+ddddMMh  ${c2}dMM   :hNMNMNhNMNMNh: ${c1}`NMm
+    NMm  ${c2}dMM  .NMN/-+MMM+-/NMN` ${c1}dMM
+    NMm  ${c2}dMM  -MMm  `MMM   dMM. ${c1}dMM
+    NMm  ${c2}dMM  -MMm  `MMM   dMM. ${c1}dMM
+    NMm  ${c2}dMM  .mmd  `mmm   yMM. ${c1}dMM
+    NMm  ${c2}dMM`  ..`   ...   ydm. ${c1}dMM
+    hMM- ${c2}+MMd/-------...-:sdds  ${c1}dMM
+    -NMm- ${c2}:hNMNNNmdddddddddy/`  ${c1}dMM
+     -dMNs-``${c2}-::::-------.``    ${c1}dMM
+      `/dMNmy+/:-------------:/yMMM
+         ./ydNMMMMMMMMMMMMMMMMMMMMM
+            .MMMMMMMMMMMMMMMMMMM"#;
+
+pub const POP_OS: &str = r#"${c1}             /////////////
+         /////////////////////
+      ///////${c2}*767${c1}////////////////
+    //////${c2}7676767676*${c1}//////////////
+   /////${c2}76767${c1}//${c2}7676767${c1}//////////////
+  /////${c2}767676${c1}///${c2}*76767${c1}///////////////
+ ///////${c2}767676${c1}///${c2}76767.${c1}///${c2}7676*${c1}///////
+/////////${c2}767676${c1}//${c2}76767${c1}///${c2}767676${c1}////////
+//////////${c2}76767676767${c1}////${c2}76767${c1}/////////
+///////////${c2}76767676${c1}//////${c2}7676${c1}//////////
+////////////${c2},7676,${c1}///////${c2}767${c1}///////////
+/////////////${c2}*7676${c1}///////${c2}76${c1}////////////
+///////////////${c2}7676${c1}////////////////////
+ ///////////////${c2}7676${c1}///${c2}767${c1}////////////
+  //////////////////////${c2}'${c1}////////////
+   //////${c2}.7676767676767676767,${c1}//////
+    /////${c2}767676767676767676767${c1}/////
+      ///////////////////////////
+         /////////////////////
+             /////////////"#;
+
+pub const ROCKY_LINUX: &str = r#"${c1}          __wgliliiligw_,
+       _williiiiiiliilililw,
+     _%iiiiiilililiiiiiiiiiii_
+   .Qliiiilililiiiiiiililililiil,
+  {liiilililiiiiiiiiiiiiiiiiiiiiii>
+ {liiiiiiiiiiiiiiiiiiiiiiiiiiiiiii>
+{liiiiiiiiiiiiiiiiiii@iiiiiiiiiiiiii}
+liiiiiiiiiiiiiiiii@@@@&liiiiiiiiiiii
+liiiiiiiiiiiiiii@@@@@@@@iiiiiiiiiiii
+|iiiiiiiiiiiiii@@@@@@@@@@%iiiiiiiii]
+ iiiiiiiiiiii@@@@@@@@@@@@@@iiiiiiii'
+ 'liiiiiiii@@@@@@@@@@@@@@@@@liiiii'
+   iiiiii@@@@@@@@@@@@@@@@@@@@@ii'
+    `~iii@@@@@@@@@@@@@@@@@@@@~
+        `~~@@@@@@@@@@@@@@~~`"#;
+
+pub const ALMA_LINUX: &str = r#"${c1}         'c:.
+        lkkkx, ..       ${c2}..   ,cc,
+${c1}        okkkk:ckkx'  ${c2}.lxkkx.okkkkd
+${c1}        .:llcokkx'  ${c2}:kkkxkko:xkkd,
+${c1}      .xkkkkdood:  ${c2};kx,  .lkxlll;
+${c1}       xkkx.       ${c2}xk'     xkkkkk:
+${c1}       'xkx.       ${c2}xd      .....,.
+${c3}      .. ${c1}:xkl'     ${c2}:c      ..''..
+${c3}    .dkx'  ${c1}.:ldl:'. ${c2}'  ${c4}':lollldkkxo;
+${c3}  .''lkkko'                     ${c4}ckkkx.
+${c3}'xkkkd:kkd.       ..  ${c5};'        ${c4}:kkxo.
+${c3},xkkkd;kk'      ,d;    ${c5}ld.   ${c4}':dkd::cc,
+${c3} .,,.;xkko'.';lxo.      ${c5}dx,  ${c4}:kkk'xkkkkc
+${c3}     'dkkkkkxo:.        ${c5};kx  ${c4}.kkk:;xkkd.
+${c3}       .....   ${c5}.;dk:.   ${c5}lkk.  ${c4}:;,
+             ${c5}:kkkkkkkdoxkkx
+              ,c,,;;;:xkkd.
+                ;kkkkl...
+                ;kkkkl
+                 ,od;"#;
+
+pub const RHEL: &str = r#"${c1}           .MMM..:MMMMMMM
+          MMMMMMMMMMMMMMMMMM
+          MMMMMMMMMMMMMMMMMMMM.
+         MMMMMMMMMMMMMMMMMMMMMM
+        ,MMMMMMMMMMMMMMMMMMMMMM:
+        MMMMMMMMMMMMMMMMMMMMMMMM
+  .MMMM'  MMMMMMMMMMMMMMMMMMMMMM
+ MMMMMM    `MMMMMMMMMMMMMMMMMMMM.
+MMMMMMMM      MMMMMMMMMMMMMMMMMM .
+MMMMMMMMM.       `MMMMMMMMMMMMM' MM.
+MMMMMMMMMMM.                     MMMM
+`MMMMMMMMMMMMM.                 ,MMMMM.
+ `MMMMMMMMMMMMMMMMM.          ,MMMMMMMM.
+    MMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMM
+      MMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMMM:
+         MMMMMMMMMMMMMMMMMMMMMMMMMMMMMM
+            `MMMMMMMMMMMMMMMMMMMMMMMM:
+                ``MMMMMMMMMMMMMMMMM'"#;
+
+pub const CENTOS: &str = r#"${c1}                 ..
+               .PLTJ.
+              <><><><>
+     ${c2}KKSSV' 4KKK ${c1}LJ${c4} KKKL.'VSSKK
+     ${c2}KKV' 4KKKKK ${c1}LJ${c4} KKKKAL 'VKK
+     ${c2}V' ' 'VKKKK ${c1}LJ${c4} KKKKV' ' 'V
+     ${c2}.4MA.' 'VKK ${c1}LJ${c4} KKV' '.4Mb.
+${c4}   . ${c2}KKKKKA.' 'V ${c1}LJ${c4} V' '.4KKKKK ${c3}.
+${c4} .4D ${c2}KKKKKKKA.'' ${c1}LJ${c4} ''.4KKKKKKK ${c3}FA.
+${c4}<QDD ++++++++++++  ${c3}++++++++++++ GFD>
+${c4} 'VD ${c3}KKKKKKKK'.. ${c2}LJ ${c1}..'KKKKKKKK ${c3}FV
+${c4}   ' ${c3}VKKKKK'. .4 ${c2}LJ ${c1}K. .'KKKKKV ${c3}'
+     ${c3}'VK'. .4KK ${c2}LJ ${c1}KKA. .'KV'
+     ${c3}A. . .4KKKK ${c2}LJ ${c1}KKKKA. . .4
+     ${c3}KKA. 'KKKKK ${c2}LJ ${c1}KKKKK' .4KK
+     ${c3}KKSSA. VKKK ${c2}LJ ${c1}KKKV .4SSKK
+${c2}              <><><><>
+               'MKKM'
+                 ''"#;
+
+pub const KALI_LINUX: &str = r#"${c1}..............
+            ..,;:ccc,.
+          ......''';lxO.
+.....''''..........,:ld;
+           .';;;:::;,,.x,
+      ..'''.            0Xxoc:,.  ...
+  ....                ,ONkc;,;cokOdc',.
+ .                   OMo           ':${c2}dd${c1}o.
+                    dMc               :OO;
+                    0M.                 .:o.
+                    ;Wd
+                     ;XO,
+                       ,d0Odlc;,..
+                           ..',;:cdOOd::,.
+                                    .:d;.':;.
+                                       'd,  .'
+                                         ;l   ..
+                                          .o
+                                            c
+                                            .'
+                                             ."#;
+
+pub const ELEMENTARY: &str = r#"${c1}         eeeeeeeeeeeeeeeee
+      eeeeeeeeeeeeeeeeeeeeeee
+    eeeee  eeeeeeeeeeee   eeeee
+  eeee   eeeee       eee     eeee
+ eeee   eeee          eee     eeee
+eee    eee            eee       eee
+eee   eee            eee        eee
+ee    eee           eeee       eeee
+ee    eee         eeeee      eeeeee
+ee    eee       eeeee      eeeee ee
+eee   eeee   eeeeee      eeeee  eee
+eee    eeeeeeeeee     eeeeee    eee
+ eeeeeeeeeeeeeeeeeeeeeeee    eeeee
+  eeeeeeee eeeeeeeeeeee      eeee
+    eeeee                 eeeee
+      eeeeeee         eeeeeee
+         eeeeeeeeeeeeeeeee"#;
+
+pub const FREEBSD: &str = r#"${c2}```                        ${c1}`
+  ${c2}` `.....---...${c1}....--.```   -/
+  ${c2}+o   .--`         ${c1}/y:`      +.
+  ${c2} yo`:.            ${c1}:o      `+-
+    ${c2}y/               ${c1}-/`   -o/
+   ${c2}.-                  ${c1}::/sy+:.
+   ${c2}/                     ${c1}`--  /
+  ${c2}`:                          ${c1}:`
+  ${c2}`:                          ${c1}:`
+   ${c2}/                          ${c1}/
+   ${c2}.-                        ${c1}-.
+    ${c2}--                      ${c1}-.
+     ${c2}`:`                  ${c1}`:`
+       .--             `--.
+          .---.....----."#;
+
+pub const RASPBERRY_PI_OS: &str = r#"${c1}  `.::///+:/-.        --///+//-:``
+ `+oooooooooooo:   `+oooooooooooo:
+  /oooo++//ooooo:  ooooo+//+ooooo.
+  `+ooooooo:-:oo-  +o+::/ooooooo:
+   `:oooooooo+``    `.oooooooo+-
+     `:++ooo/.        :+ooo+/.`
+${c2}        ...`  `.----.` ``..
+     .::::-``:::::::::.`-:::-`
+    -:::-`   .:::::::-`  `-:::-
+   `::.  `.--.`  `` `.---.``.::`
+       .::::::::`  -::::::::` `
+ .::` .:::::::::- `::::::::::``::.
+-:::` ::::::::::.  ::::::::::.`:::-
+::::  -::::::::.   `-::::::-  ::::
+-::-   .-:::-.``....``.-::-.   -::-
+ .. ``       .::::::::.     `..`..
+   -:::-`   -::::::::::`  .:::::`
+   :::::::` -::::::::::` :::::::.
+   .:::::::  -::::::::. ::::::::
+    `-:::::`   ..--.`   ::::::.
+      `...`  `...--..`  `...`
+            .::::::::::
+             `.-::::-`"#;
+
+pub const ARCH_LINUX_SMALL: &str = r#"${c1}      /\
+     /  \
+    /\   \
+${c2}   /      \
+  /   ,,   \
+ /   |  |  -\
+/_-''    ''-_\"#;
+
+pub const ALPINE_LINUX_SMALL: &str = r#"${c1}   /\ /\
+  // \  \
+ //   \  \
+///    \  \
+//      \  \
+         \"#;
+
+pub const DEBIAN_SMALL: &str = r#"${c1}  _____
+ /  __ \
+|  /    |
+|  \___-
+-_
+  --_"#;
+
+pub const WINDOWS_10_11_SMALL: &str = r#"${c1}######## ########
+######## ########
+######## ########
+
+######## ########
+######## ########
+######## ########"#;
+
+pub const WINDOWS_SMALL: &str = r#"${c1}        ,.=:!!t3Z3z.,
+       :tt:::tt333EE3
+       Et:::ztt33EEE
+      Et:,:ztt33EEE
+     Et:::zt333EEE
+    Et:::ztt33EEE
+   ""::,..:;:!!t3"#;
+
+pub const MACOS_SMALL: &str = r#"${c1}        .:'
+    __ :'__
+${c2} .'`  `-'  ``.
+${c3}:          .-'
+${c4}:         :
+${c5} :         `-;
+${c6}  `.__.-.__.'"#;
+
+pub const UNKNOWN_SMALL: &str = r#"${c1}    ___
+   (.. |
+   (<> |
+  / __  \
+ ( /  \ /|
+_/\ __)/_)
+\/-____\/"#;
+
+pub const UBUNTU_SMALL: &str = r#"${c1}         _
+     ---(_)
+ _/  ---  \
+(_) |   |
+  \  --- _/
+     ---(_)"#;
+
+pub const FEDORA_SMALL: &str = r#"${c1}        ,'''''.
+       |   ,.  |
+       |  |  '_'
+  ,....|  |..
+.'  ,_;|   ..'
+|  |   |  |
+|  ',_,'  |
+ '.     ,'
+   '''''"#;
+
+pub const OPENSUSE_SMALL: &str = r#"${c1}  _______
+__|   __ \
+     / .\ \
+     \__/ |
+   _______|
+   \_______
+__________/"#;
+
+pub const NIXOS_SMALL: &str = r#"${c1}  \\  \\ //
+ ==\\__\\/ //
+   //   \\//
+==//     //==
+ //\\___//
+// /\\  \\==
+  // \\  \\"#;
+
+pub const GENTOO_SMALL: &str = r#"${c1} _-----_
+(       \
+\    0   \
+${c2} \        )
+ /      _/
+(     _-
+\____-"#;
+
+pub const VOID_SMALL: &str = r#"${c1}    _______
+ _ \______ -
+| \  ___  \ |
+| | /   \ | |
+| | \___/ | |
+| \______ \_|
+ -_______\"#;
+
+pub const MANJARO_SMALL: &str = r#"${c1}||||||||| ||||
+||||||||| ||||
+||||      ||||
+|||| |||| ||||
+|||| |||| ||||
+|||| |||| ||||
+|||| |||| ||||"#;
+
+pub const LINUX_MINT_SMALL: &str = r#"${c1} ___________
+|_          \
+  | ${c2}| _____ ${c1}|
+  | ${c2}| | | | ${c1}|
+  | ${c2}| | | | ${c1}|
+  | ${c2}\_____/ ${c1}|
+  \_________/"#;
+
+pub const POP_OS_SMALL: &str = r#"${c1}______
+\   _ \        __
+ \ \ \ \      / /
+  \ \_\ \    / /
+   \  ___\  /_/
+    \ \    _
+   __\_\__(_)_
+  (___________)`"#;
+
+pub const ROCKY_LINUX_SMALL: &str = r#"${c1}    `-/+++++++/-`
+  `/syyyyyyyyyyyyo/`
+ /yyyyyyyyyyyyyyyyyy/
+:yyyyyyyyyy/ `+yyyyyy:
+yyyyyyyyy/    `+yyyyyy
+yyyyyy+.        .+yyy
+:yyy+.          .+yy:
+ /y/           .+yy/
+  `-          -+o-`"#;
+
+pub const ALMA_LINUX_SMALL: &str = r#"${c1}   .-'''-.
+${c2}  /  .-.  \
+${c3} |  (   )  |
+${c4}  \  '-'  /
+${c5}   '-._.-'"#;
+
+pub const RHEL_SMALL: &str = r#"${c1}      .M.:MMM
+     MMMMMMMMMM.
+    ,MMMMMMMMMMM
+ .MM MMMMMMMMMMM
+MMMM   MMMMMMMMM
+MMMMMM        MM
+ MMMMMMMMM   ,MMMM
+   MMMMMMMMMMMMMMMM:
+      `MMMMMMMMMMMM"#;
+
+pub const CENTOS_SMALL: &str = r#"${c1} ____${c2}^${c3}____
+${c1} |\  ${c2}|${c3}  /|
+${c1} | \ ${c2}|${c3} / |
+${c4}<---- ${c1}---->
+${c3} | / ${c4}|${c1} \ |
+${c3} |/__${c4}|${c1}__\|
+     ${c4}v"#;
+
+pub const KALI_LINUX_SMALL: &str = r#"${c1} ..,;:cc,.
+......''';lx.
+  ..'''.   0Xx.
+ .       OMo  ':d.
+        dMc     :O
+         ;XO,
+           ,d0Odl;.
+                 .:d."#;
+
+pub const ELEMENTARY_SMALL: &str = r#"${c1}  _______
+ / ____  \
+/  |  /  /\
+|__\ /  / |
+\   /__/  /
+ \_______/"#;
+
+pub const FREEBSD_SMALL: &str = r#"${c1}/\,-'''''-,/\
+\_)       (_/
+|           |
+|           |
+ ;         ;
+  '-_____-'"#;
+
+pub const RASPBERRY_PI_OS_SMALL: &str = r#"${c1}   .~~.   .~~.
+  '. \ ' ' / .'
+${c2}   .~ .~~~..~.
+  : .~.'~'.~. :
+ ~ (   ) (   ) ~
+( : '~'.~.'~' : )
+ ~ .~ (   ) ~. ~
+  (  : '~' :  )
+   '~ .~~~. ~'
+       '~'"#;
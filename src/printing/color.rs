@@ -0,0 +1,230 @@
+use crate::config::Combine;
+use serde::{Deserialize, Serialize};
+
+/// Resets all colors and styles
+pub const RESET: &str = "\x1b[0m";
+
+/// When to color the output
+#[derive(
+    Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice
+{
+    Always,
+    /// Color when stdout is a terminal and `NO_COLOR` isn't set
+    #[default]
+    Auto,
+    Never,
+}
+
+impl ColorChoice
+{
+    /// Whether output should be colored
+    pub fn enabled(&self) -> bool
+    {
+        match self
+        {
+            ColorChoice::Always =>
+            {
+                enable_virtual_terminal();
+                true
+            }
+            ColorChoice::Never => false,
+            ColorChoice::Auto =>
+            {
+                let no_color = std::env::var_os("NO_COLOR")
+                    .map(|v| !v.is_empty())
+                    .unwrap_or(false);
+                !no_color && super::layout::stdout_is_tty() && enable_virtual_terminal()
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for ColorChoice
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        match s
+        {
+            "always" => Ok(ColorChoice::Always),
+            "auto" => Ok(ColorChoice::Auto),
+            "never" => Ok(ColorChoice::Never),
+            _ =>
+            {
+                Err(format!(
+                    "invalid color choice '{s}' (expected always, auto, or never)"
+                ))
+            }
+        }
+    }
+}
+
+/// Windows consoles only interpret escape sequences once asked to
+#[cfg(windows)]
+fn enable_virtual_terminal() -> bool
+{
+    use windows::Win32::System::Console::{
+        GetConsoleMode, GetStdHandle, SetConsoleMode, CONSOLE_MODE,
+        ENABLE_VIRTUAL_TERMINAL_PROCESSING, STD_OUTPUT_HANDLE,
+    };
+
+    unsafe {
+        let Ok(handle) = GetStdHandle(STD_OUTPUT_HANDLE)
+        else
+        {
+            return false;
+        };
+        let mut mode = CONSOLE_MODE::default();
+        GetConsoleMode(handle, &mut mode).as_bool()
+            && SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING).as_bool()
+    }
+}
+
+#[cfg(not(windows))]
+fn enable_virtual_terminal() -> bool { true }
+
+/// A terminal color: 0-7 are the standard colors, 8-15 their bright
+/// variants, and 16-255 the rest of the 256-color palette
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Serialize, Deserialize)]
+#[serde(try_from = "ColorRepr", into = "ColorRepr")]
+pub struct Color(pub u8);
+
+const COLOR_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+impl Color
+{
+    /// The escape sequence that switches the foreground to this color
+    pub fn fg(&self) -> String
+    {
+        match self.0
+        {
+            n @ 0..=7 => format!("\x1b[3{n}m"),
+            n @ 8..=15 => format!("\x1b[9{}m", n - 8),
+            n => format!("\x1b[38;5;{n}m"),
+        }
+    }
+
+    /// `s` in this color
+    pub fn paint(&self, s: &str) -> String { format!("{}{s}{RESET}", self.fg()) }
+}
+
+impl std::str::FromStr for Color
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let name = s.trim().to_lowercase();
+        if let Ok(n) = name.parse()
+        {
+            return Ok(Color(n));
+        }
+
+        let (bright, base) = match name.strip_prefix("bright-")
+        {
+            Some(base) => (8, base),
+            None => (0, name.as_str()),
+        };
+        COLOR_NAMES
+            .iter()
+            .position(|&color| color == base)
+            .map(|n| Color(n as u8 + bright))
+            .ok_or_else(|| {
+                format!(
+                    "invalid color '{s}' (expected 0-255 or one of {}, optionally prefixed with \
+                     'bright-')",
+                    COLOR_NAMES.join(", ")
+                )
+            })
+    }
+}
+
+/// Colors can be written as a name or a palette number
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum ColorRepr
+{
+    Number(u8),
+    Name(String),
+}
+
+impl TryFrom<ColorRepr> for Color
+{
+    type Error = String;
+
+    fn try_from(repr: ColorRepr) -> Result<Self, Self::Error>
+    {
+        match repr
+        {
+            ColorRepr::Number(n) => Ok(Color(n)),
+            ColorRepr::Name(name) => name.parse(),
+        }
+    }
+}
+
+impl From<Color> for ColorRepr
+{
+    fn from(color: Color) -> Self
+    {
+        match color.0
+        {
+            n @ 0..=7 => ColorRepr::Name(COLOR_NAMES[n as usize].to_string()),
+            n @ 8..=15 => ColorRepr::Name(format!("bright-{}", COLOR_NAMES[n as usize - 8])),
+            n => ColorRepr::Number(n),
+        }
+    }
+}
+
+/// The `[theme]` table of the config file
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Theme
+{
+    /// The color of labels like `CPU:`, defaulting to the logo's first color
+    pub label: Option<Color>,
+
+    /// Replaces the logo's colors, `${c1}` first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub logo: Vec<Color>,
+}
+
+/// Prefers `other`'s colors over `self`'s
+impl Combine for Theme
+{
+    fn combine(&mut self, other: Self)
+    {
+        self.label = other.label.or(self.label);
+        if !other.logo.is_empty()
+        {
+            self.logo = other.logo;
+        }
+    }
+}
+
+impl Theme
+{
+    /// The colors to draw a logo with: the theme's, then the logo's own
+    pub fn palette(&self, logo_colors: &[u8]) -> Vec<Color>
+    {
+        (0..self.logo.len().max(logo_colors.len()))
+            .map(|i| {
+                self.logo
+                    .get(i)
+                    .copied()
+                    .unwrap_or_else(|| Color(logo_colors[i]))
+            })
+            .collect()
+    }
+
+    /// The color of labels
+    pub fn label_color(&self, palette: &[Color]) -> Option<Color>
+    {
+        self.label.or_else(|| palette.first().copied())
+    }
+}
@@ -0,0 +1,530 @@
+//! Drawing the logo as an image with the kitty, iTerm2 or sixel graphics
+//! protocols.
+//!
+//! The image is sized in terminal cells and drawn in space reserved for it, so
+//! the information can be printed beside it just like beside the character
+//! art.
+
+use super::*;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::{
+    collections::BTreeSet,
+    io,
+    path::{Path, PathBuf},
+};
+
+/// The cell size to assume when the terminal doesn't report one
+const DEFAULT_CELL_SIZE: (usize, usize) = (8, 16);
+
+/// The most base64 the kitty protocol accepts in one escape sequence
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// A graphics protocol to draw images with
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageProtocol
+{
+    Kitty,
+    Iterm2,
+    Sixel,
+}
+
+impl ImageProtocol
+{
+    /// Guess the protocol the terminal supports from its environment
+    pub fn detect() -> Option<Self> { Self::detect_from(|name| std::env::var(name).ok()) }
+
+    fn detect_from(var: impl Fn(&str) -> Option<String>) -> Option<Self>
+    {
+        let term = var("TERM").unwrap_or_default();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+
+        if var("KITTY_WINDOW_ID").is_some() || term == "xterm-kitty" || program == "ghostty"
+        {
+            Some(ImageProtocol::Kitty)
+        }
+        else if matches!(program.as_str(), "iTerm.app" | "WezTerm" | "mintty")
+            || var("LC_TERMINAL").as_deref() == Some("iTerm2")
+        {
+            Some(ImageProtocol::Iterm2)
+        }
+        else if term.contains("sixel")
+            || term.starts_with("foot")
+            || term == "mlterm"
+            || var("KONSOLE_VERSION").is_some()
+        {
+            Some(ImageProtocol::Sixel)
+        }
+        else
+        {
+            None
+        }
+    }
+}
+
+impl std::str::FromStr for ImageProtocol
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        match s
+        {
+            "kitty" => Ok(ImageProtocol::Kitty),
+            "iterm2" => Ok(ImageProtocol::Iterm2),
+            "sixel" => Ok(ImageProtocol::Sixel),
+            _ =>
+            {
+                Err(format!(
+                    "invalid image protocol '{s}' (expected kitty, iterm2, or sixel)"
+                ))
+            }
+        }
+    }
+}
+
+/// A decoded PNG image
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Image
+{
+    /// The PNG itself, which kitty and iTerm2 are sent as is
+    png: Vec<u8>,
+    width: usize,
+    height: usize,
+    /// 8-bit RGBA pixels, row by row
+    rgba: Vec<u8>,
+}
+
+impl Image
+{
+    pub fn load(path: &Path) -> io::Result<Self> { Self::from_png(std::fs::read(path)?) }
+
+    pub fn from_png(png: Vec<u8>) -> io::Result<Self>
+    {
+        let invalid = |e| io::Error::new(io::ErrorKind::InvalidData, e);
+
+        let mut decoder = png::Decoder::new(png.as_slice());
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+        let mut reader = decoder.read_info().map_err(invalid)?;
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).map_err(invalid)?;
+        buf.truncate(info.buffer_size());
+
+        let rgba = match info.color_type
+        {
+            png::ColorType::Rgba => buf,
+            png::ColorType::Rgb =>
+            {
+                buf.chunks(3)
+                    .flat_map(|p| [p[0], p[1], p[2], 255])
+                    .collect()
+            }
+            png::ColorType::GrayscaleAlpha =>
+            {
+                buf.chunks(2)
+                    .flat_map(|p| [p[0], p[0], p[0], p[1]])
+                    .collect()
+            }
+            png::ColorType::Grayscale => buf.iter().flat_map(|&v| [v, v, v, 255]).collect(),
+            // `EXPAND` turns indexed colors into RGB(A)
+            png::ColorType::Indexed =>
+            {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "unexpected indexed PNG",
+                ))
+            }
+        };
+
+        Ok(Self {
+            width: info.width as usize,
+            height: info.height as usize,
+            png,
+            rgba,
+        })
+    }
+
+    /// The number of rows the image takes up when `cols` wide, keeping its
+    /// aspect ratio
+    pub fn rows(&self, cols: usize, cell: (usize, usize)) -> usize
+    {
+        let pixel_height = (cols * cell.0 * self.height) as f64 / self.width.max(1) as f64;
+        ((pixel_height / cell.1 as f64).round() as usize).max(1)
+    }
+
+    /// The escape sequence that draws the image `cols` by `rows` cells large at
+    /// the cursor, leaving the cursor where it was
+    pub fn encode(
+        &self,
+        protocol: ImageProtocol,
+        cols: usize,
+        rows: usize,
+        cell: (usize, usize),
+    ) -> String
+    {
+        match protocol
+        {
+            ImageProtocol::Kitty => self.encode_kitty(cols, rows),
+            ImageProtocol::Iterm2 =>
+            {
+                format!(
+                    "\x1b7\x1b]1337;File=inline=1;size={};width={cols};height={rows};\
+                     preserveAspectRatio=0:{}\x07\x1b8",
+                    self.png.len(),
+                    STANDARD.encode(&self.png)
+                )
+            }
+            ImageProtocol::Sixel =>
+            {
+                format!(
+                    "\x1b7{}\x1b8",
+                    self.encode_sixel(cols * cell.0, rows * cell.1)
+                )
+            }
+        }
+    }
+
+    fn encode_kitty(&self, cols: usize, rows: usize) -> String
+    {
+        let data = STANDARD.encode(&self.png);
+        let chunks: Vec<&[u8]> = data.as_bytes().chunks(KITTY_CHUNK_SIZE).collect();
+
+        let mut s = String::new();
+        for (i, chunk) in chunks.iter().enumerate()
+        {
+            let more = u8::from(i + 1 < chunks.len());
+            let chunk = std::str::from_utf8(chunk).unwrap();
+            if i == 0
+            {
+                // `C=1` keeps the cursor still and `q=2` silences responses
+                s.push_str(&format!(
+                    "\x1b_Ga=T,f=100,q=2,C=1,c={cols},r={rows},m={more};{chunk}\x1b\\"
+                ));
+            }
+            else
+            {
+                s.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+            }
+        }
+        s
+    }
+
+    /// Encode the image scaled to `width` by `height` pixels as sixels, with
+    /// its colors reduced to a 6x6x6 color cube and transparent pixels left
+    /// as the background
+    fn encode_sixel(&self, width: usize, height: usize) -> String
+    {
+        // The palette index of each pixel, if it isn't transparent
+        let pixels: Vec<Option<usize>> = (0..width * height)
+            .map(|i| {
+                let x = (i % width) * self.width / width;
+                let y = (i / width) * self.height / height;
+                let p = &self.rgba[(y * self.width + x) * 4..][..4];
+                let level = |v: u8| (v as usize * 5 + 127) / 255;
+                (p[3] >= 128).then(|| level(p[0]) * 36 + level(p[1]) * 6 + level(p[2]))
+            })
+            .collect();
+
+        let mut s = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+        let used: BTreeSet<usize> = pixels.iter().flatten().copied().collect();
+        for &color in &used
+        {
+            let percent = |level: usize| level * 100 / 5;
+            s.push_str(&format!(
+                "#{color};2;{};{};{}",
+                percent(color / 36),
+                percent(color / 6 % 6),
+                percent(color % 6)
+            ));
+        }
+
+        // Each sixel is a column of six pixels
+        for top in (0..height).step_by(6)
+        {
+            let band: BTreeSet<usize> = (top..(top + 6).min(height))
+                .flat_map(|y| pixels[y * width..][..width].iter().flatten().copied())
+                .collect();
+            for (i, &color) in band.iter().enumerate()
+            {
+                if i > 0
+                {
+                    s.push('$');
+                }
+                s.push_str(&format!("#{color}"));
+
+                let sixels = (0..width).map(|x| {
+                    let bits = (0..6)
+                        .filter(|dy| {
+                            top + dy < height && pixels[(top + dy) * width + x] == Some(color)
+                        })
+                        .fold(0, |bits, dy| bits | 1 << dy);
+                    (63 + bits) as u8 as char
+                });
+                push_run_length_encoded(&mut s, sixels);
+            }
+            s.push('-');
+        }
+        s.push_str("\x1b\\");
+        s
+    }
+}
+
+/// Append `sixels`, writing runs of four or more as `!<count><sixel>`
+fn push_run_length_encoded(s: &mut String, sixels: impl Iterator<Item = char>)
+{
+    let mut run: Option<(char, usize)> = None;
+    let flush = |s: &mut String, run: Option<(char, usize)>| {
+        match run
+        {
+            Some((c, n)) if n >= 4 => s.push_str(&format!("!{n}{c}")),
+            Some((c, n)) => s.push_str(&c.to_string().repeat(n)),
+            None => (),
+        }
+    };
+    for c in sixels
+    {
+        run = match run
+        {
+            Some((prev, n)) if prev == c => Some((c, n + 1)),
+            _ =>
+            {
+                flush(s, run);
+                Some((c, 1))
+            }
+        };
+    }
+    flush(s, run);
+}
+
+/// The distribution's own logo (os-release's `LOGO` icon), when it's
+/// installed as a PNG
+#[cfg(target_os = "linux")]
+pub fn distribution_logo() -> Option<PathBuf>
+{
+    let icon = crate::info::system::os_release().ok()?.logo?;
+    let candidates = [
+        format!("/usr/share/pixmaps/{icon}.png"),
+        format!("/usr/share/icons/hicolor/256x256/apps/{icon}.png"),
+        format!("/usr/share/icons/hicolor/128x128/apps/{icon}.png"),
+        format!("/usr/share/icons/hicolor/512x512/apps/{icon}.png"),
+    ];
+    candidates
+        .into_iter()
+        .map(PathBuf::from)
+        .find(|path| path.is_file())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn distribution_logo() -> Option<PathBuf> { None }
+
+/// A logo ready to be drawn as an image
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ImageLogo
+{
+    pub image: Image,
+    pub protocol: ImageProtocol,
+    /// The size of the image in cells
+    pub cols: usize,
+    pub rows: usize,
+    cell: (usize, usize),
+}
+
+impl ImageLogo
+{
+    /// Prepare `image` to be drawn `cols` cells wide with `protocol`
+    pub fn new(image: Image, protocol: ImageProtocol, cols: usize) -> Self
+    {
+        let cell = layout::cell_size().unwrap_or(DEFAULT_CELL_SIZE);
+        let rows = image.rows(cols, cell);
+        Self {
+            image,
+            protocol,
+            cols,
+            rows,
+            cell,
+        }
+    }
+
+    /// The image logo to draw, if the terminal supports images (or `protocol`
+    /// is given) and there's an image: `file`, or else the distribution's logo
+    pub fn find(file: Option<&Path>, protocol: Option<ImageProtocol>, cols: usize) -> Option<Self>
+    {
+        let protocol = protocol.or_else(|| {
+            layout::stdout_is_tty()
+                .then(ImageProtocol::detect)
+                .flatten()
+        })?;
+        let path = file.map(Path::to_path_buf).or_else(distribution_logo)?;
+        match Image::load(&path)
+        {
+            Ok(image) => Some(Self::new(image, protocol, cols)),
+            Err(e) =>
+            {
+                eprintln!("Couldn't load image '{}': {e}", path.display());
+                None
+            }
+        }
+    }
+
+    /// Whether the image and `s` fit side by side in `width` columns
+    pub fn fits(&self, s: &str, width: Option<usize>) -> bool
+    {
+        match width
+        {
+            Some(width) => self.cols + 2 + min_max_line_len(s).1 <= width,
+            None => true,
+        }
+    }
+
+    /// Draw the image with `s` beside it.
+    ///
+    /// Space for the image is reserved first (scrolling the terminal if need
+    /// be), then the image is drawn without moving the cursor and each line of
+    /// `s` is moved past it.
+    pub fn with(&self, s: &str) -> String
+    {
+        let lines: Vec<&str> = s.split('\n').collect();
+        let mut out = "\n".repeat(self.rows);
+        out.push_str(&format!("\x1b[{}A", self.rows));
+        out.push_str(
+            &self
+                .image
+                .encode(self.protocol, self.cols, self.rows, self.cell),
+        );
+        for line in &lines
+        {
+            out.push_str(&format!("\x1b[{}C{line}\n", self.cols + 2));
+        }
+        out.push_str(&"\n".repeat(self.rows.saturating_sub(lines.len())));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// A 2x2 image: red and green on top, blue and a transparent pixel below
+    fn image() -> Image
+    {
+        Image {
+            png: b"not really a png".to_vec(),
+            width: 2,
+            height: 2,
+            rgba: vec![255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 0, 0, 0, 0],
+        }
+    }
+
+    #[test]
+    fn encodes_kitty()
+    {
+        assert_eq!(
+            image().encode(ImageProtocol::Kitty, 4, 2, (8, 16)),
+            "\x1b_Ga=T,f=100,q=2,C=1,c=4,r=2,m=0;bm90IHJlYWxseSBhIHBuZw==\x1b\\"
+        );
+    }
+
+    #[test]
+    fn chunks_kitty()
+    {
+        let mut image = image();
+        image.png = vec![0; KITTY_CHUNK_SIZE];
+        let encoded = image.encode(ImageProtocol::Kitty, 4, 2, (8, 16));
+        let chunks: Vec<&str> = encoded.split("\x1b\\").filter(|c| !c.is_empty()).collect();
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[0].starts_with("\x1b_Ga=T,f=100,q=2,C=1,c=4,r=2,m=1;"));
+        assert!(chunks[1].starts_with("\x1b_Gm=0;"));
+    }
+
+    #[test]
+    fn encodes_iterm2()
+    {
+        assert_eq!(
+            image().encode(ImageProtocol::Iterm2, 4, 2, (8, 16)),
+            "\x1b7\x1b]1337;File=inline=1;size=16;width=4;height=2;preserveAspectRatio=0:\
+             bm90IHJlYWxseSBhIHBuZw==\x07\x1b8"
+        );
+    }
+
+    #[test]
+    fn encodes_sixel()
+    {
+        // Scaled to 4x4 pixels: red and green in the top two rows, blue and
+        // transparent in the bottom two
+        assert_eq!(
+            image().encode(ImageProtocol::Sixel, 2, 1, (2, 4)),
+            "\x1b7\x1bP0;1;0q\"1;1;4;4#5;2;0;0;100#30;2;0;100;0#180;2;100;0;0#5KK??$#30??BB$#\
+             180BB??-\x1b\\\x1b8"
+        );
+    }
+
+    #[test]
+    fn run_length_encodes_sixels()
+    {
+        let mut s = String::new();
+        push_run_length_encoded(&mut s, "aaabbbbc".chars());
+        assert_eq!(s, "aaa!4bc");
+    }
+
+    #[test]
+    fn draws_image_beside_info()
+    {
+        let logo = ImageLogo {
+            image: image(),
+            protocol: ImageProtocol::Kitty,
+            cols: 4,
+            rows: 3,
+            cell: (8, 16),
+        };
+        assert_eq!(
+            logo.with("CPU: x\nRAM: y"),
+            concat!(
+                "\n\n\n\x1b[3A",
+                "\x1b_Ga=T,f=100,q=2,C=1,c=4,r=3,m=0;bm90IHJlYWxseSBhIHBuZw==\x1b\\",
+                "\x1b[6CCPU: x\n\x1b[6CRAM: y\n\n",
+            )
+        );
+    }
+
+    #[test]
+    fn decodes_png()
+    {
+        let mut png = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut png, 2, 1);
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(&[255, 0, 0, 0, 0, 255]).unwrap();
+        }
+
+        let image = Image::from_png(png).unwrap();
+        assert_eq!((image.width, image.height), (2, 1));
+        assert_eq!(image.rgba, [255, 0, 0, 255, 0, 0, 255, 255]);
+        assert_eq!(image.rows(10, (8, 16)), 3);
+        assert!(Image::from_png(b"GIF89a".to_vec()).is_err());
+    }
+
+    #[test]
+    fn detects_protocols()
+    {
+        let detect = |vars: &[(&str, &str)]| {
+            ImageProtocol::detect_from(|name| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            })
+        };
+        assert_eq!(
+            detect(&[("TERM", "xterm-kitty")]),
+            Some(ImageProtocol::Kitty)
+        );
+        assert_eq!(
+            detect(&[("TERM_PROGRAM", "iTerm.app")]),
+            Some(ImageProtocol::Iterm2)
+        );
+        assert_eq!(detect(&[("TERM", "foot")]), Some(ImageProtocol::Sixel));
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), None);
+    }
+}
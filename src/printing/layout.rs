@@ -0,0 +1,171 @@
+use super::*;
+
+/// Whether stdout is connected to a terminal
+pub fn stdout_is_tty() -> bool { unsafe { libc::isatty(1) != 0 } }
+
+/// The width of the terminal stdout is connected to, falling back to the
+/// `COLUMNS` environment variable
+pub fn terminal_width() -> Option<usize>
+{
+    console_width()
+        .or_else(|| std::env::var("COLUMNS").ok()?.trim().parse().ok())
+        .filter(|&width| width > 0)
+}
+
+#[cfg(unix)]
+fn console_width() -> Option<usize>
+{
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } < 0
+    {
+        return None;
+    }
+    Some(size.ws_col as usize)
+}
+
+#[cfg(windows)]
+fn console_width() -> Option<usize>
+{
+    use windows::Win32::System::Console::{
+        GetConsoleScreenBufferInfo, GetStdHandle, CONSOLE_SCREEN_BUFFER_INFO, STD_OUTPUT_HANDLE,
+    };
+
+    unsafe {
+        let handle = GetStdHandle(STD_OUTPUT_HANDLE).ok()?;
+        let mut info = CONSOLE_SCREEN_BUFFER_INFO::default();
+        if !GetConsoleScreenBufferInfo(handle, &mut info).as_bool()
+        {
+            return None;
+        }
+        Some((info.srWindow.Right - info.srWindow.Left + 1) as usize)
+    }
+}
+
+/// The size of a terminal cell in pixels, when the terminal reports it
+#[cfg(unix)]
+pub fn cell_size() -> Option<(usize, usize)>
+{
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } < 0
+        || size.ws_col == 0
+        || size.ws_row == 0
+        || size.ws_xpixel == 0
+        || size.ws_ypixel == 0
+    {
+        return None;
+    }
+    Some((
+        (size.ws_xpixel / size.ws_col) as usize,
+        (size.ws_ypixel / size.ws_row) as usize,
+    ))
+}
+
+#[cfg(windows)]
+pub fn cell_size() -> Option<(usize, usize)> { None }
+
+/// How the art and information are arranged
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct Layout
+{
+    /// Use the small variant of the art
+    pub small: bool,
+
+    /// Print the art above the information rather than beside it
+    pub vertical: bool,
+
+    /// Don't print the art
+    pub omit_art: bool,
+}
+
+impl Layout
+{
+    /// Adjust the layout so the output fits in `width` columns.
+    ///
+    /// When the art and information don't fit side by side, the small art is
+    /// tried, then the art above the information (the small art if the large
+    /// doesn't fit), then no art. Lines of information that are still too long
+    /// are ellipsized, which is what this returns.
+    pub fn fit(&mut self, art: &Art, info: &str, width: usize) -> String
+    {
+        let (_, info_width) = min_max_line_len(info);
+        let art_width = |small| art.width(small);
+
+        if !self.omit_art && !self.vertical
+        {
+            if art_width(self.small) + 2 + info_width <= width
+            {
+                return info.to_string();
+            }
+            if art_width(true) + 2 + info_width <= width
+            {
+                self.small = true;
+                return info.to_string();
+            }
+            self.vertical = true;
+        }
+
+        if !self.omit_art && art_width(self.small) > width
+        {
+            if art_width(true) <= width
+            {
+                self.small = true;
+            }
+            else
+            {
+                self.omit_art = true;
+            }
+        }
+
+        info.lines()
+            .map(|line| ellipsize(line, width))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    const INFO: &str = "CPU: AMD Ryzen 5 5600X 6-Core Processor@3.70GHz\nRAM: 1.2 GiB/31.3 GiB";
+
+    #[test]
+    fn keeps_a_layout_that_fits()
+    {
+        let mut layout = Layout::default();
+        assert_eq!(layout.fit(&Art::Builtin(OsArt::ArchLinux), INFO, 200), INFO);
+        assert_eq!(layout, Layout::default());
+    }
+
+    #[test]
+    fn falls_back_to_small_then_vertical_art()
+    {
+        let art = Art::Builtin(OsArt::ArchLinux);
+
+        let mut layout = Layout::default();
+        assert_eq!(layout.fit(&art, INFO, 70), INFO);
+        assert!(layout.small && !layout.vertical);
+
+        let mut layout = Layout::default();
+        assert_eq!(layout.fit(&art, INFO, 50), INFO);
+        assert!(!layout.small && layout.vertical);
+    }
+
+    #[test]
+    fn ellipsizes_what_still_does_not_fit()
+    {
+        let mut layout = Layout::default();
+        let info = layout.fit(&Art::Builtin(OsArt::ArchLinux), INFO, 30);
+        assert_eq!(
+            info,
+            "CPU: AMD Ryzen 5 5600X 6-Core…\nRAM: 1.2 GiB/31.3 GiB"
+        );
+        assert!(layout.small && layout.vertical && !layout.omit_art);
+
+        let colored = color_labels(INFO, Color(4));
+        let info = Layout::default().fit(&Art::Builtin(OsArt::ArchLinux), &colored, 10);
+        assert_eq!(strip_ansi(&info), "CPU: AMD…\nRAM: 1.2…");
+        assert!(info.lines().all(|line| line.ends_with(RESET)));
+    }
+}
@@ -1,13 +1,72 @@
 pub mod ascii_art;
+pub mod color;
+pub mod image;
+pub mod layout;
+pub mod redact;
+pub mod template;
 pub use ascii_art::*;
+use color::{Color, RESET};
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
-#[allow(dead_code)]
-pub enum OsArt
+/// The maximum number of distinct colors a logo can use (`${c1}`..`${c6}`)
+pub const MAX_LOGO_COLORS: usize = 6;
+
+/// A built-in logo.
+///
+/// The art marks where colors change with `${c1}`..`${c6}` placeholders, which
+/// refer to the entries of `colors` (ANSI color numbers, 0-7).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Logo
 {
+    pub art: &'static str,
+    pub small: &'static str,
+    pub colors: &'static [u8],
+}
+
+/// Declare [`OsArt`] along with [`OsArt::ALL`], so the list can't miss a
+/// variant
+macro_rules! os_arts {
+    ($($(#[$attr:meta])* $variant:ident,)*) => {
+        #[derive(
+            Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default, Serialize, Deserialize,
+        )]
+        #[serde(try_from = "String", into = "String")]
+        pub enum OsArt
+        {
+            $($(#[$attr])* $variant,)*
+        }
+
+        impl OsArt
+        {
+            /// Every variant, in declaration order
+            pub const ALL: &'static [OsArt] = &[$(OsArt::$variant,)*];
+        }
+    };
+}
+
+os_arts! {
     ArchLinux,
     AlpineLinux,
     Debian,
+    Ubuntu,
+    Fedora,
+    OpenSuse,
+    NixOs,
+    Gentoo,
+    Void,
+    Manjaro,
+    LinuxMint,
+    PopOs,
+    RockyLinux,
+    AlmaLinux,
+    Rhel,
+    CentOs,
+    KaliLinux,
+    Elementary,
+    RaspberryPiOs,
+    FreeBsd,
     Windows,
     Windows1011,
     MacOS,
@@ -15,38 +74,399 @@ pub enum OsArt
     Unknown,
 }
 
+impl OsArt
+{
+    /// The name to pick the logo by with `--logo`
+    pub fn name(&self) -> &'static str
+    {
+        match self
+        {
+            OsArt::ArchLinux => "arch",
+            OsArt::AlpineLinux => "alpine",
+            OsArt::Debian => "debian",
+            OsArt::Ubuntu => "ubuntu",
+            OsArt::Fedora => "fedora",
+            OsArt::OpenSuse => "opensuse",
+            OsArt::NixOs => "nixos",
+            OsArt::Gentoo => "gentoo",
+            OsArt::Void => "void",
+            OsArt::Manjaro => "manjaro",
+            OsArt::LinuxMint => "mint",
+            OsArt::PopOs => "pop",
+            OsArt::RockyLinux => "rocky",
+            OsArt::AlmaLinux => "alma",
+            OsArt::Rhel => "rhel",
+            OsArt::CentOs => "centos",
+            OsArt::KaliLinux => "kali",
+            OsArt::Elementary => "elementary",
+            OsArt::RaspberryPiOs => "raspberry-pi",
+            OsArt::FreeBsd => "freebsd",
+            OsArt::Windows => "windows",
+            OsArt::Windows1011 => "windows11",
+            OsArt::MacOS => "macos",
+            OsArt::Unknown => "tux",
+        }
+    }
+
+    pub fn logo(&self) -> Logo
+    {
+        let (art, small, colors): (_, _, &[u8]) = match self
+        {
+            OsArt::ArchLinux => (ARCH_LINUX, ARCH_LINUX_SMALL, &[6, 6]),
+            OsArt::AlpineLinux => (ALPINE_LINUX, ALPINE_LINUX_SMALL, &[4]),
+            OsArt::Debian => (DEBIAN, DEBIAN_SMALL, &[1]),
+            OsArt::Ubuntu => (UBUNTU, UBUNTU_SMALL, &[1, 7]),
+            OsArt::Fedora => (FEDORA, FEDORA_SMALL, &[4, 7]),
+            OsArt::OpenSuse => (OPENSUSE, OPENSUSE_SMALL, &[2, 7]),
+            OsArt::NixOs => (NIXOS, NIXOS_SMALL, &[4, 6]),
+            OsArt::Gentoo => (GENTOO, GENTOO_SMALL, &[5, 7]),
+            OsArt::Void => (VOID, VOID_SMALL, &[2, 0]),
+            OsArt::Manjaro => (MANJARO, MANJARO_SMALL, &[2]),
+            OsArt::LinuxMint => (LINUX_MINT, LINUX_MINT_SMALL, &[2, 7]),
+            OsArt::PopOs => (POP_OS, POP_OS_SMALL, &[6, 7]),
+            OsArt::RockyLinux => (ROCKY_LINUX, ROCKY_LINUX_SMALL, &[2]),
+            OsArt::AlmaLinux => (ALMA_LINUX, ALMA_LINUX_SMALL, &[3, 1, 4, 2, 6]),
+            OsArt::Rhel => (RHEL, RHEL_SMALL, &[1]),
+            OsArt::CentOs => (CENTOS, CENTOS_SMALL, &[3, 2, 4, 5]),
+            OsArt::KaliLinux => (KALI_LINUX, KALI_LINUX_SMALL, &[4, 0]),
+            OsArt::Elementary => (ELEMENTARY, ELEMENTARY_SMALL, &[7]),
+            OsArt::RaspberryPiOs => (RASPBERRY_PI_OS, RASPBERRY_PI_OS_SMALL, &[2, 1]),
+            OsArt::FreeBsd => (FREEBSD, FREEBSD_SMALL, &[1, 7]),
+            OsArt::Windows1011 => (WINDOWS_10_11, WINDOWS_10_11_SMALL, &[6]),
+            OsArt::Windows => (WINDOWS, WINDOWS_SMALL, &[4]),
+            OsArt::MacOS => (MACOS, MACOS_SMALL, &[2, 3, 1, 1, 5, 4]),
+            OsArt::Unknown => (UNKNOWN, UNKNOWN_SMALL, &[3]),
+        };
+        Logo { art, small, colors }
+    }
+
+    /// The art with its color placeholders removed and every line padded to
+    /// the width of the widest one
+    pub fn render(&self, small: bool) -> String { self.paint(small, None) }
+
+    /// Like [`OsArt::render`], but drawing the art in `palette`'s colors when
+    /// given one
+    pub fn paint(&self, small: bool, palette: Option<&[Color]>) -> String
+    {
+        let logo = self.logo();
+        render_art(if small { logo.small } else { logo.art }, palette)
+    }
+}
+
 impl std::fmt::Display for OsArt
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
-        let art = match self
+        write!(f, "{}", self.render(false))
+    }
+}
+
+impl std::str::FromStr for OsArt
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let name = s.trim().to_lowercase();
+        OsArt::ALL
+            .iter()
+            .copied()
+            .find(|art| art.name() == name)
+            .ok_or_else(|| {
+                let names: Vec<_> = OsArt::ALL.iter().map(OsArt::name).collect();
+                format!("unknown logo '{s}' (expected one of {})", names.join(", "))
+            })
+    }
+}
+
+impl TryFrom<String> for OsArt
+{
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> { s.parse() }
+}
+
+impl From<OsArt> for String
+{
+    fn from(art: OsArt) -> Self { art.name().to_string() }
+}
+
+/// The art printed beside the information
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum Art
+{
+    /// One of the built-in logos
+    Builtin(OsArt),
+
+    /// The user's own art, which may use `${c1}`..`${c6}` color placeholders
+    Custom(String),
+}
+
+impl Art
+{
+    /// Read custom art from a file
+    pub fn from_file(path: &std::path::Path) -> std::io::Result<Self>
+    {
+        let art = std::fs::read_to_string(path)?;
+        Ok(Art::Custom(
+            art.trim_end_matches(['\n', '\r']).replace('\t', "    "),
+        ))
+    }
+
+    /// The width of the art in columns
+    pub fn width(&self, small: bool) -> usize { min_max_line_len(&self.paint(small, None)).1 }
+
+    /// Render the art, drawing it in `palette`'s colors when given one.
+    /// Custom art has no small variant.
+    pub fn paint(&self, small: bool, palette: Option<&[Color]>) -> String
+    {
+        match self
         {
-            OsArt::ArchLinux => ARCH_LINUX,
-            OsArt::AlpineLinux => ALPINE_LINUX,
-            OsArt::Debian => DEBIAN,
-            OsArt::Windows1011 => WINDOWS_10_11,
-            OsArt::Windows => WINDOWS,
-            OsArt::MacOS => MACOS,
-            _ => UNKNOWN,
-        };
+            Art::Builtin(os) => os.paint(small, palette),
+            Art::Custom(art) => render_art(art, palette),
+        }
+    }
+}
+
+/// Render art containing `${c1}`..`${c6}` placeholders, padding every line to
+/// the same width.
+///
+/// With a palette, each line starts in the color in effect where the last one
+/// ended and is reset at its end, so the art can be printed beside other text.
+/// Without one, the placeholders are removed.
+pub fn render_art(art: &str, palette: Option<&[Color]>) -> String
+{
+    let (_, width) = min_max_line_len(&strip_color_placeholders(art));
+    let mut current: Option<Color> = None;
+
+    art.lines()
+        .map(|line| {
+            let mut rendered = String::new();
+            let mut visible = String::new();
+            if let Some(color) = current.filter(|_| palette.is_some())
+            {
+                rendered.push_str(&color.fg());
+            }
+
+            let mut rest = line;
+            while !rest.is_empty()
+            {
+                if let Some((n, after)) = color_placeholder(rest)
+                {
+                    if let Some(palette) = palette
+                    {
+                        current = palette.get(n - 1).copied();
+                        match current
+                        {
+                            Some(color) => rendered.push_str(&color.fg()),
+                            None => rendered.push_str(RESET),
+                        }
+                    }
+                    rest = after;
+                    continue;
+                }
+
+                let c = rest.chars().next().unwrap();
+                rendered.push(c);
+                visible.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+
+            if palette.is_some()
+            {
+                rendered.push_str(RESET);
+            }
+            rendered.push_str(&" ".repeat(width.saturating_sub(display_width(&visible))));
+            rendered
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// If `s` starts with a `${cN}` placeholder, its number and the rest of `s`
+fn color_placeholder(s: &str) -> Option<(usize, &str)>
+{
+    let rest = s.strip_prefix("${c")?;
+    let (n, rest) = rest.split_once('}')?;
+    let n = n.parse().ok()?;
+    (1..=MAX_LOGO_COLORS).contains(&n).then_some((n, rest))
+}
+
+/// Remove the `${c1}`..`${c6}` color placeholders from a piece of art
+pub fn strip_color_placeholders(art: &str) -> String
+{
+    let mut stripped = art.to_string();
+    for n in 1..=MAX_LOGO_COLORS
+    {
+        stripped = stripped.replace(&format!("${{c{n}}}"), "");
+    }
+    stripped
+}
+
+/// Color the label (e.g. `CPU:`) at the start of each line of information
+pub fn color_labels(info: &str, color: Color) -> String
+{
+    info.lines()
+        .map(|line| {
+            // Indented lines continue the one above
+            if line.starts_with(char::is_whitespace)
+            {
+                return line.to_string();
+            }
+            let end = match line.find(':')
+            {
+                Some(colon) => colon + 1,
+                None => line.find(' ').unwrap_or(line.len()),
+            };
+            format!("{}{}", color.paint(&line[..end]), &line[end..])
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn print_with_logo(art: &Art, small: bool, palette: Option<&[Color]>, center: bool, s: &str)
+{
+    print!("{}", with_both(&art.paint(small, palette), s, center));
+}
 
-        write!(f, "{art}")
+/// Split `s` into runs of text and ANSI escape sequences (colors, hyperlinks,
+/// ...), marking which are escapes
+fn ansi_segments(s: &str) -> Vec<(&str, bool)>
+{
+    let mut segments = Vec::new();
+    let mut text_start = 0;
+    let mut chars = s.char_indices().peekable();
+    while let Some((start, c)) = chars.next()
+    {
+        if c != '\x1b'
+        {
+            continue;
+        }
+
+        match chars.next()
+        {
+            // CSI: `ESC [`, parameters, then a final byte
+            Some((_, '[')) =>
+            {
+                for (_, c) in chars.by_ref()
+                {
+                    if ('@'..='~').contains(&c)
+                    {
+                        break;
+                    }
+                }
+            }
+            // OSC: `ESC ]`, terminated by BEL or `ESC \`
+            Some((_, ']')) =>
+            {
+                while let Some((_, c)) = chars.next()
+                {
+                    if c == '\x07' || (c == '\x1b' && chars.next_if(|&(_, c)| c == '\\').is_some())
+                    {
+                        break;
+                    }
+                }
+            }
+            // Anything else is a two character sequence
+            _ => (),
+        }
+
+        let end = chars.peek().map_or(s.len(), |&(i, _)| i);
+        if text_start < start
+        {
+            segments.push((&s[text_start..start], false));
+        }
+        segments.push((&s[start..end], true));
+        text_start = end;
     }
+    if text_start < s.len()
+    {
+        segments.push((&s[text_start..], false));
+    }
+    segments
+}
+
+/// Remove ANSI escape sequences from `s`
+fn strip_ansi(s: &str) -> String
+{
+    ansi_segments(s)
+        .into_iter()
+        .filter(|&(_, escape)| !escape)
+        .map(|(text, _)| text)
+        .collect()
 }
 
-pub fn print_with_logo(os: OsArt, s: &str)
+/// The number of terminal columns a grapheme cluster takes up
+fn grapheme_width(grapheme: &str) -> usize
 {
-    print!("{}", with_both(&os.to_string(), s));
+    let width = grapheme.width();
+    // Emoji sequences joined into one cluster are drawn as one glyph
+    if grapheme.chars().nth(1).is_some()
+    {
+        width.min(2)
+    }
+    else
+    {
+        width
+    }
 }
 
-/// Returns the length of the longest line in the string
+/// The number of terminal columns `s` takes up, ignoring ANSI escape sequences
+/// and counting wide (e.g. CJK) characters as two columns
+fn display_width(s: &str) -> usize { strip_ansi(s).graphemes(true).map(grapheme_width).sum() }
+
+/// Shorten `line` to at most `width` columns, ending it with an ellipsis if
+/// anything was cut. Escape sequences are kept and colors reset after the cut.
+fn ellipsize(line: &str, width: usize) -> String
+{
+    if display_width(line) <= width
+    {
+        return line.to_string();
+    }
+
+    let budget = width.saturating_sub(1);
+    let mut used = 0;
+    let mut shortened = String::new();
+    let mut escaped = false;
+    'segments: for (segment, escape) in ansi_segments(line)
+    {
+        if escape
+        {
+            shortened.push_str(segment);
+            escaped = true;
+            continue;
+        }
+        for grapheme in segment.graphemes(true)
+        {
+            used += grapheme_width(grapheme);
+            if used > budget
+            {
+                break 'segments;
+            }
+            shortened.push_str(grapheme);
+        }
+    }
+
+    if width > 0
+    {
+        shortened.truncate(shortened.trim_end().len());
+        shortened.push('…');
+    }
+    if escaped
+    {
+        shortened.push_str(RESET);
+    }
+    shortened
+}
+
+/// Returns the display widths of the shortest and longest lines in the string
 fn min_max_line_len(s: &str) -> (usize, usize)
 {
     let mut max_len = 0;
-    let mut min_len = usize::max_value();
+    let mut min_len = usize::MAX;
     for line in s.lines()
     {
-        let len = line.len();
+        let len = display_width(line);
         if len > max_len
         {
             max_len = len;
@@ -59,13 +479,27 @@ fn min_max_line_len(s: &str) -> (usize, usize)
     (min_len, max_len)
 }
 
-
-fn with_both(first: &str, second: &str) -> String
+/// Lay `first` and `second` out side by side. With `center`, the shorter of
+/// the two is vertically centered against the taller one.
+fn with_both(first: &str, second: &str, center: bool) -> String
 {
     let (_, max_len) = min_max_line_len(first);
 
-    let first: Vec<&str> = first.split('\n').collect();
-    let second: Vec<&str> = second.split('\n').collect();
+    let mut first: Vec<&str> = first.split('\n').collect();
+    let mut second: Vec<&str> = second.split('\n').collect();
+    if center
+    {
+        let (shorter, taller) = if first.len() < second.len()
+        {
+            (&mut first, second.len())
+        }
+        else
+        {
+            (&mut second, first.len())
+        };
+        let offset = (taller - shorter.len()) / 2;
+        shorter.splice(0..0, vec![""; offset]);
+    }
     let mut s = String::new();
 
     let mut i = 0;
@@ -75,10 +509,139 @@ fn with_both(first: &str, second: &str) -> String
         let sec = second.get(i).unwrap_or(&"");
 
         s.push_str(fir);
-        s.push_str(&" ".repeat(max_len - fir.len() + 2));
+        s.push_str(&" ".repeat(max_len - display_width(fir) + 2));
         s.push_str(&format!("{sec}\n"));
 
         i += 1;
     }
     s
 }
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// Every `${cN}` placeholder in `art`
+    fn placeholders(art: &str) -> Vec<usize>
+    {
+        art.match_indices("${c")
+            .map(|(i, _)| {
+                let rest = &art[i + 3..];
+                let end = rest.find('}').expect("unterminated color placeholder");
+                rest[..end].parse().expect("invalid color placeholder")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_logo_has_art()
+    {
+        for &os in OsArt::ALL
+        {
+            let logo = os.logo();
+            for art in [logo.art, logo.small]
+            {
+                assert!(!strip_color_placeholders(art).trim().is_empty(), "{os:?}");
+                assert!(!art.contains('\t'), "{os:?} art contains a tab");
+                assert!(art.is_ascii(), "{os:?} art isn't ASCII");
+            }
+        }
+    }
+
+    #[test]
+    fn logo_placeholders_have_colors()
+    {
+        for &os in OsArt::ALL
+        {
+            let logo = os.logo();
+            assert!(!logo.colors.is_empty() && logo.colors.len() <= MAX_LOGO_COLORS);
+            assert!(logo.colors.iter().all(|&c| c < 8), "{os:?}");
+            for art in [logo.art, logo.small]
+            {
+                assert!(
+                    art.starts_with("${c"),
+                    "{os:?} art doesn't start with a color"
+                );
+                for n in placeholders(art)
+                {
+                    assert!(
+                        (1..=logo.colors.len()).contains(&n),
+                        "{os:?} uses ${{c{n}}}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn logo_names_round_trip()
+    {
+        for &os in OsArt::ALL
+        {
+            assert_eq!(os.name().parse::<OsArt>(), Ok(os));
+        }
+        assert!("Arch".parse::<OsArt>().is_ok());
+        assert!("templeos".parse::<OsArt>().is_err());
+    }
+
+    #[test]
+    fn display_width_ignores_escapes_and_counts_wide_characters()
+    {
+        assert_eq!(display_width("CPU:"), 4);
+        assert_eq!(display_width(&Color(4).paint("CPU:")), 4);
+        assert_eq!(
+            display_width("\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x07"),
+            4
+        );
+        assert_eq!(display_width("╭──╮"), 4);
+        assert_eq!(display_width("ホスト"), 6);
+        assert_eq!(display_width("e\u{301}"), 1);
+    }
+
+    #[test]
+    fn with_both_aligns_and_centers()
+    {
+        let art = format!("{}\n⣿⣿⣿\n{}", Color(1).paint("ab"), "日本");
+        assert_eq!(
+            strip_ansi(&with_both(&art, "A\nB\nC", false)),
+            "ab    A\n⣿⣿⣿   B\n日本  C\n"
+        );
+        assert_eq!(
+            strip_ansi(&with_both(&art, "B", true)),
+            "ab    \n⣿⣿⣿   B\n日本  \n"
+        );
+    }
+
+    #[test]
+    fn logo_line_widths_are_consistent()
+    {
+        for &os in OsArt::ALL
+        {
+            let logo = os.logo();
+            let (large, small) = (
+                strip_color_placeholders(logo.art),
+                strip_color_placeholders(logo.small),
+            );
+            let (_, large_width) = min_max_line_len(&large);
+            let (_, small_width) = min_max_line_len(&small);
+            assert!(large_width <= 48, "{os:?} is {large_width} columns wide");
+            assert!(
+                small_width <= 24,
+                "{os:?} small is {small_width} columns wide"
+            );
+            assert!(small.lines().count() <= large.lines().count(), "{os:?}");
+
+            // Rendering pads ragged lines out to the widest one
+            for (small, width) in [(false, large_width), (true, small_width)]
+            {
+                let rendered = os.render(small);
+                assert!(
+                    !rendered.contains("${c"),
+                    "{os:?} has a leftover placeholder"
+                );
+                assert_eq!(min_max_line_len(&rendered), (width, width), "{os:?}");
+            }
+        }
+    }
+}
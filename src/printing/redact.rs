@@ -0,0 +1,430 @@
+//! Masking sensitive values in the output, so it can be shared publicly.
+//!
+//! Names (hostnames, user names, and Wi-Fi networks) are masked in the fields
+//! they're read into, before they're printed, so the same word elsewhere in the
+//! output is left alone. Values with a recognisable form (IP and MAC addresses,
+//! and serial numbers) are found in the printed text.
+//!
+//! Each value is replaced with a placeholder naming what it was and a hash of
+//! it (e.g. `<host-3fa2c1>`). The hash is keyed with a secret kept on this
+//! machine, so the same value is always masked the same way here, and two
+//! reports from one machine can be compared, but the value can't be guessed
+//! from its placeholder.
+
+use crate::info::Fields;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Deserialize;
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    net::IpAddr,
+    path::PathBuf,
+};
+
+lazy_static! {
+    static ref MAC: Regex = Regex::new(r"\b[0-9A-Fa-f]{2}(?:[:-][0-9A-Fa-f]{2}){5}\b").unwrap();
+    static ref IPV4: Regex = Regex::new(r"\b\d{1,3}(?:\.\d{1,3}){3}\b").unwrap();
+    static ref IPV6: Regex = Regex::new(r"[0-9A-Fa-f]*:[0-9A-Fa-f:.]*[0-9A-Fa-f]").unwrap();
+    static ref SERIAL: Regex =
+        Regex::new(r"(?i)(\bserial(?:[ _-]?(?:number|no\.?))?\s*[:=]\s*)([^\s,;()]+)").unwrap();
+}
+
+/// The `[redact]` table: which values are masked
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(default)]
+pub struct Rules
+{
+    /// The hostname, and the hosts users are logged in from
+    pub hostname: bool,
+
+    /// The names of the caller and logged-in users
+    pub user: bool,
+
+    /// IP addresses, except loopback ones
+    pub ip: bool,
+
+    /// MAC addresses
+    pub mac: bool,
+
+    /// Values labelled as serial numbers (e.g. `Serial: XYZ123`)
+    pub serial: bool,
+
+    /// The connected Wi-Fi network's name
+    pub ssid: bool,
+
+    /// Regular expressions matching anything else to mask
+    #[serde(deserialize_with = "one_or_more")]
+    pub patterns: Vec<String>,
+
+    /// The secret placeholders are derived with, instead of this machine's
+    pub key: Option<String>,
+}
+
+impl Default for Rules
+{
+    fn default() -> Self
+    {
+        Self {
+            hostname: true,
+            user: true,
+            ip: true,
+            mac: true,
+            serial: true,
+            ssid: true,
+            patterns: Vec::new(),
+            key: None,
+        }
+    }
+}
+
+/// A list, or a single item (e.g. from an environment variable, where a comma
+/// could be part of a pattern)
+fn one_or_more<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error>
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMore
+    {
+        One(String),
+        More(Vec<String>),
+    }
+
+    Ok(match OneOrMore::deserialize(deserializer)?
+    {
+        OneOrMore::One(item) => vec![item],
+        OneOrMore::More(items) => items,
+    })
+}
+
+/// What a masked value was
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Kind
+{
+    Host,
+    User,
+    Ip,
+    Mac,
+    Serial,
+    Ssid,
+    /// Matched by one of the `patterns`
+    Other,
+}
+
+impl Kind
+{
+    fn name(self) -> &'static str
+    {
+        match self
+        {
+            Self::Host => "host",
+            Self::User => "user",
+            Self::Ip => "ip",
+            Self::Mac => "mac",
+            Self::Serial => "serial",
+            Self::Ssid => "ssid",
+            Self::Other => "redacted",
+        }
+    }
+
+    fn enabled(self, rules: &Rules) -> bool
+    {
+        match self
+        {
+            Self::Host => rules.hostname,
+            Self::User => rules.user,
+            Self::Ip => rules.ip,
+            Self::Mac => rules.mac,
+            Self::Serial => rules.serial,
+            Self::Ssid => rules.ssid,
+            Self::Other => true,
+        }
+    }
+}
+
+/// The fields of each module that hold names to mask
+const FIELDS: [(&str, &str, Kind); 5] = [
+    ("host", "hostname", Kind::Host),
+    ("users", "sessions.host", Kind::Host),
+    ("caller", "name", Kind::User),
+    ("users", "sessions.user", Kind::User),
+    ("net", "wireless.ssid", Kind::Ssid),
+];
+
+/// Masks the sensitive values in modules' fields and in text
+#[derive(Debug, Clone)]
+pub struct Redactor
+{
+    rules: Rules,
+    key: String,
+    patterns: Vec<Regex>,
+}
+
+impl Redactor
+{
+    /// A redactor deriving placeholders from `key`. Patterns that aren't
+    /// valid regular expressions are reported and ignored.
+    pub fn new(rules: Rules, key: String) -> Self
+    {
+        let patterns = rules
+            .patterns
+            .iter()
+            .filter_map(|pattern| {
+                Regex::new(pattern)
+                    .map_err(|e| eprintln!("Ignoring redaction pattern '{pattern}': {e}"))
+                    .ok()
+            })
+            .collect();
+        Self {
+            rules,
+            key,
+            patterns,
+        }
+    }
+
+    /// A redactor with the configured key, or else this machine's, which is
+    /// made the first time it's needed
+    pub fn load(rules: Rules) -> Self
+    {
+        let key = rules.key.clone().unwrap_or_else(machine_key);
+        Self::new(rules, key)
+    }
+
+    /// Mask the names in the `module`'s fields, before they're printed
+    pub fn redact_fields<T: Fields + ?Sized>(&self, module: &str, info: &mut T)
+    {
+        for (path, text) in info.text_fields_mut()
+        {
+            let Some((.., kind)) = FIELDS
+                .iter()
+                .find(|(name, field, _)| *name == module && *field == path)
+            else
+            {
+                continue;
+            };
+            let value = text.trim();
+            // X displays (e.g. `:0`) aren't hosts
+            if kind.enabled(&self.rules) && !value.is_empty() && !value.starts_with(':')
+            {
+                *text = self.placeholder(*kind, value);
+            }
+        }
+    }
+
+    /// `text` with its IP and MAC addresses, serial numbers, and matches of the
+    /// `patterns` masked
+    pub fn redact(&self, text: &str) -> String
+    {
+        let mut text = text.to_string();
+        for pattern in &self.patterns
+        {
+            text = pattern
+                .replace_all(&text, |captures: &regex::Captures| {
+                    self.placeholder(Kind::Other, &captures[0])
+                })
+                .into_owned();
+        }
+        if self.rules.serial
+        {
+            text = SERIAL
+                .replace_all(&text, |captures: &regex::Captures| {
+                    let serial = self.placeholder(Kind::Serial, &captures[2]);
+                    format!("{}{serial}", &captures[1])
+                })
+                .into_owned();
+        }
+        if self.rules.mac
+        {
+            text = MAC
+                .replace_all(&text, |captures: &regex::Captures| {
+                    self.placeholder(Kind::Mac, &captures[0].to_lowercase().replace('-', ":"))
+                })
+                .into_owned();
+        }
+        if self.rules.ip
+        {
+            for pattern in [&*IPV4, &*IPV6]
+            {
+                text = pattern
+                    .replace_all(&text, |captures: &regex::Captures| {
+                        match captures[0].parse::<IpAddr>()
+                        {
+                            Ok(ip) if !ip.is_loopback() && !ip.is_unspecified() =>
+                            {
+                                self.placeholder(Kind::Ip, &ip.to_string())
+                            }
+                            _ => captures[0].to_string(),
+                        }
+                    })
+                    .into_owned();
+            }
+        }
+        text
+    }
+
+    /// The placeholder for `value`
+    fn placeholder(&self, kind: Kind, value: &str) -> String
+    {
+        let hash = fnv1a([self.key.as_str(), kind.name(), value]);
+        format!("<{}-{:06x}>", kind.name(), hash & 0xFF_FFFF)
+    }
+}
+
+/// The 64-bit FNV-1a hash of `parts`, which is stable between runs and
+/// versions, unlike the standard library's hashers
+fn fnv1a<'a>(parts: impl IntoIterator<Item = &'a str>) -> u64
+{
+    let mut hash: u64 = 0xCBF2_9CE4_8422_2325;
+    for part in parts
+    {
+        // Separate the parts, so e.g. ("ab", "c") and ("a", "bc") differ
+        for byte in part.bytes().chain(std::iter::once(0))
+        {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01B3);
+        }
+    }
+    hash
+}
+
+/// Where this machine's redaction key is kept
+fn key_path() -> Option<PathBuf> { Some(dirs::data_local_dir()?.join("rinfo").join("redact.key")) }
+
+/// This machine's redaction key, made and saved the first time it's needed.
+/// If it can't be saved, placeholders will differ between runs.
+fn machine_key() -> String
+{
+    let path = key_path();
+    if let Some(key) = path
+        .as_ref()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+    {
+        return key;
+    }
+
+    // The standard library seeds each `RandomState` randomly
+    let key: String = (0..4)
+        .map(|_| format!("{:016x}", RandomState::new().build_hasher().finish()))
+        .collect();
+    let saved = path.as_ref().map(|path| {
+        path.parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(path, &key))
+    });
+    if let Some(Err(e)) = saved
+    {
+        eprintln!("Couldn't save the redaction key, so placeholders will differ between runs: {e}");
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::info::{Host, Session, Users};
+
+    fn redactor(rules: Rules) -> Redactor { Redactor::new(rules, "key".to_string()) }
+
+    fn users() -> Users
+    {
+        let session = |user: &str, host: Option<&str>| {
+            Session {
+                user: user.to_string(),
+                tty: "pts/0".to_string(),
+                host: host.map(str::to_string),
+                login_time: 0,
+            }
+        };
+        Users {
+            sessions: vec![
+                session("user", Some(":0")),
+                session("bob", Some("ubuntu.lan")),
+            ],
+        }
+    }
+
+    #[test]
+    fn masks_names_only_in_their_fields()
+    {
+        let redactor = redactor(Rules::default());
+        let mut host = Host {
+            hostname: "ubuntu".to_string(),
+        };
+        let mut users = users();
+        redactor.redact_fields("host", &mut host);
+        redactor.redact_fields("users", &mut users);
+
+        assert_eq!(host.hostname, redactor.placeholder(Kind::Host, "ubuntu"));
+        assert_eq!(
+            users.sessions[0].user,
+            redactor.placeholder(Kind::User, "user")
+        );
+        assert_eq!(users.sessions[0].host.as_deref(), Some(":0"));
+        assert_eq!(
+            users.sessions[1].host,
+            Some(redactor.placeholder(Kind::Host, "ubuntu.lan"))
+        );
+
+        // The hostname and user name are also words in other lines
+        let text = redactor.redact(&format!("OS: Ubuntu 22.04\n{host}\n{users}"));
+        assert!(text.starts_with("OS: Ubuntu 22.04\nHOST: <host-"), "{text}");
+        assert!(text.contains("\nUSERS: 2 (<"), "{text}");
+        assert!(!text.contains("bob"), "{text}");
+    }
+
+    #[test]
+    fn masks_values_deterministically()
+    {
+        let redactor = redactor(Rules::default());
+        let text = concat!(
+            "LAN: 192.168.1.18 (IPV4), 127.0.0.1, fe80::1c2b:3aff:fe4d:5e6f\n",
+            "GATEWAY: 192.168.1.1 (wlan0)\n",
+            "BT: AA:BB:CC:DD:EE:FF, serial number: XYZ-123\n",
+        );
+        let redacted = redactor.redact(text);
+        for hidden in ["192.168", "fe80", "AA:BB", "XYZ"]
+        {
+            assert!(!redacted.contains(hidden), "{hidden} in {redacted}");
+        }
+        assert!(redacted.contains("127.0.0.1"));
+        assert!(redacted.contains("serial number: <serial-"));
+        assert!(redacted.starts_with("LAN: <ip-"));
+
+        // The same values, the same placeholders
+        assert_eq!(redactor.redact(text), redacted);
+        assert_eq!(
+            redactor.redact("aa-bb-cc-dd-ee-ff"),
+            redactor.redact("AA:BB:CC:DD:EE:FF")
+        );
+        let other = Redactor::new(Rules::default(), "other".to_string());
+        assert_ne!(
+            other.redact("192.168.1.18"),
+            redactor.redact("192.168.1.18")
+        );
+    }
+
+    #[test]
+    fn follows_rules()
+    {
+        let rules: Rules = toml::from_str("patterns = 'project-\\w{1,3}'").unwrap();
+        assert_eq!(rules.patterns, [r"project-\w{1,3}"]);
+
+        let redactor = redactor(Rules {
+            hostname: false,
+            ip: false,
+            patterns: vec![r"project-\w+".to_string(), "(".to_string()],
+            ..Rules::default()
+        });
+        let mut users = users();
+        redactor.redact_fields("users", &mut users);
+        assert_eq!(users.sessions[1].host.as_deref(), Some("ubuntu.lan"));
+        assert!(users.sessions[1].user.starts_with("<user-"));
+
+        let redacted = redactor.redact("192.168.1.18 project-x");
+        assert!(redacted.starts_with("192.168.1.18 <redacted-"));
+        assert!(!redacted.contains("project-x"));
+    }
+}
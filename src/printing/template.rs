@@ -0,0 +1,731 @@
+//! User-defined output templates.
+//!
+//! A template is text with fields in braces, e.g. `CPU: {cpu.name} @
+//! {cpu.ghz:.1} GHz`. A field is a module and a path into its fields
+//! (`net.wireless.ssid`, `load.average.0`), optionally followed by a filter
+//! (`{memory.used|size}`) and a format spec (`{host.hostname:>20}`). Braces
+//! are written as `{{` and `}}`. Each line of the template is a line of
+//! output, and lines with a field that's unavailable are left out.
+
+use super::display_width;
+use crate::info::Value;
+use humansize::{FormatSize, BINARY};
+
+/// The filters a field can be passed through
+const FILTERS: [&str; 4] = ["size", "duration", "upper", "lower"];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Filter
+{
+    /// Bytes, as a human readable size (e.g. `1.5 GiB`)
+    Size,
+
+    /// Milliseconds, as human readable text (e.g. `3 hours and 2 minutes`)
+    Duration,
+    Upper,
+    Lower,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+enum Align
+{
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+/// A format spec: `[[fill]align][width][.precision]`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Spec
+{
+    fill: char,
+    align: Align,
+    width: usize,
+    precision: Option<usize>,
+}
+
+impl Default for Spec
+{
+    fn default() -> Self
+    {
+        Self {
+            fill: ' ',
+            align: Align::default(),
+            width: 0,
+            precision: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Field
+{
+    path: Vec<String>,
+    filter: Option<Filter>,
+    spec: Spec,
+
+    /// Where the path starts, as a 0-based line and byte offset
+    line: usize,
+    start: usize,
+}
+
+impl Field
+{
+    /// The byte range of the `index`th component of the path
+    fn component(&self, index: usize) -> (usize, usize)
+    {
+        let start = self.start
+            + self.path[..index]
+                .iter()
+                .map(|name| name.len() + 1)
+                .sum::<usize>();
+        (start, start + self.path[index].len())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Piece
+{
+    Text(String),
+    Field(Field),
+}
+
+/// An error in a template, and where it is
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TemplateError
+{
+    pub message: String,
+
+    /// The 1-based line and column the error is at
+    pub line: usize,
+    pub column: usize,
+
+    /// The line of the template, and how many columns of it to underline
+    source: String,
+    len: usize,
+}
+
+impl TemplateError
+{
+    /// An error about `line[start..end]`
+    fn new(message: String, line: usize, source: &str, start: usize, end: usize) -> Self
+    {
+        Self {
+            message,
+            line: line + 1,
+            column: source[..start].chars().count() + 1,
+            source: source.to_string(),
+            len: display_width(&source[start..end]).max(1),
+        }
+    }
+}
+
+impl std::fmt::Display for TemplateError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let start: String = self.source.chars().take(self.column - 1).collect();
+        write!(
+            f,
+            "{} (line {}, column {})\n    {}\n    {}{}",
+            self.message,
+            self.line,
+            self.column,
+            self.source,
+            " ".repeat(display_width(&start)),
+            "^".repeat(self.len),
+        )
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// The closest of `candidates` to `name`, if any is close enough to be a
+/// likely typo
+pub fn suggest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str>
+{
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// The edit distance between two strings, counting swapped neighbours as
+/// one edit
+fn edit_distance(a: &str, b: &str) -> usize
+{
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in 0..=a.len()
+    {
+        for j in 0..=b.len()
+        {
+            distances[i][j] = if i == 0 || j == 0
+            {
+                i + j
+            }
+            else
+            {
+                let mut distance = (distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]))
+                    .min(distances[i - 1][j] + 1)
+                    .min(distances[i][j - 1] + 1);
+                if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1]
+                {
+                    distance = distance.min(distances[i - 2][j - 2] + 1);
+                }
+                distance
+            };
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+/// `message`, with a suggestion of what might have been meant
+fn did_you_mean(message: String, suggestion: Option<&str>) -> String
+{
+    match suggestion
+    {
+        Some(suggestion) => format!("{message}; did you mean `{suggestion}`?"),
+        None => message,
+    }
+}
+
+/// A parsed output template
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Template
+{
+    /// The template's lines, as they're written
+    source: Vec<String>,
+    lines: Vec<Vec<Piece>>,
+}
+
+impl Template
+{
+    pub fn parse(source: &str) -> Result<Self, TemplateError>
+    {
+        let source: Vec<String> = source.lines().map(str::to_string).collect();
+        let lines = source
+            .iter()
+            .enumerate()
+            .map(|(index, line)| parse_line(index, line))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { source, lines })
+    }
+
+    fn fields(&self) -> impl Iterator<Item = &Field>
+    {
+        self.lines.iter().flatten().filter_map(|piece| {
+            match piece
+            {
+                Piece::Field(field) => Some(field),
+                Piece::Text(_) => None,
+            }
+        })
+    }
+
+    /// Does the template reference the field at `path`, something in it, or
+    /// the map holding it? `&["cpu"]` asks whether any of the module's fields
+    /// are used.
+    pub fn uses(&self, path: &[&str]) -> bool
+    {
+        self.fields().any(|field| {
+            field
+                .path
+                .iter()
+                .zip(path)
+                .all(|(name, other)| name == other)
+        })
+    }
+
+    /// Check that every field belongs to one of `modules`
+    pub fn check(&self, modules: &[&str]) -> Result<(), TemplateError>
+    {
+        match self
+            .fields()
+            .find(|field| !modules.contains(&field.path[0].as_str()))
+        {
+            Some(field) =>
+            {
+                let name = &field.path[0];
+                Err(self.error(
+                    did_you_mean(
+                        format!("unknown module `{name}`"),
+                        suggest(name, modules.iter().copied()),
+                    ),
+                    field,
+                    0,
+                ))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Render the template with the fields of `modules` (a `Value::Map` of
+    /// each module's fields)
+    pub fn render(&self, modules: &Value) -> Result<String, TemplateError>
+    {
+        let mut output = Vec::new();
+        'lines: for pieces in &self.lines
+        {
+            let mut line = String::new();
+            for piece in pieces
+            {
+                match piece
+                {
+                    Piece::Text(text) => line.push_str(text),
+                    Piece::Field(field) =>
+                    {
+                        match self.lookup(modules, field)?
+                        {
+                            Value::None => continue 'lines,
+                            value => line.push_str(&self.format(&value, field)?),
+                        }
+                    }
+                }
+            }
+            output.push(line);
+        }
+
+        Ok(output.join("\n"))
+    }
+
+    fn error(&self, message: String, field: &Field, component: usize) -> TemplateError
+    {
+        let (start, end) = field.component(component);
+        TemplateError::new(message, field.line, &self.source[field.line], start, end)
+    }
+
+    fn lookup(&self, modules: &Value, field: &Field) -> Result<Value, TemplateError>
+    {
+        let mut value = modules;
+        for (index, name) in field.path.iter().enumerate()
+        {
+            let parent = field.path[..index].join(".");
+            value = match value
+            {
+                Value::Map(fields) =>
+                {
+                    match fields.iter().find(|(field, _)| field == name)
+                    {
+                        Some((_, value)) => value,
+                        None =>
+                        {
+                            let message = did_you_mean(
+                                format!("`{parent}` has no field `{name}`"),
+                                suggest(name, fields.iter().map(|(name, _)| *name)),
+                            );
+                            return Err(self.error(message, field, index));
+                        }
+                    }
+                }
+                Value::List(items) =>
+                {
+                    match name.parse::<usize>()
+                    {
+                        Ok(index) =>
+                        {
+                            match items.get(index)
+                            {
+                                Some(item) => item,
+                                None => return Ok(Value::None),
+                            }
+                        }
+                        Err(_) =>
+                        {
+                            let message = format!(
+                                "`{parent}` is a list, so expected an index, e.g. `{parent}.0`"
+                            );
+                            return Err(self.error(message, field, index));
+                        }
+                    }
+                }
+                Value::None => return Ok(Value::None),
+                _ =>
+                {
+                    let message = format!("`{parent}` has no fields");
+                    return Err(self.error(message, field, index));
+                }
+            };
+        }
+
+        Ok(value.clone())
+    }
+
+    fn format(&self, value: &Value, field: &Field) -> Result<String, TemplateError>
+    {
+        let spec = field.spec;
+        let number = match *value
+        {
+            Value::Integer(n) => Some(n as f64),
+            Value::Float(n) => Some(n),
+            _ => None,
+        };
+
+        let s = match field.filter
+        {
+            Some(filter @ (Filter::Size | Filter::Duration)) =>
+            {
+                let Some(number) = number.filter(|n| *n >= 0.0)
+                else
+                {
+                    let message = format!("`{}` isn't a number", field.path.join("."));
+                    return Err(self.error(message, field, field.path.len() - 1));
+                };
+
+                if filter == Filter::Size
+                {
+                    (number as u64).format_size(BINARY)
+                }
+                else
+                {
+                    use chrono_humanize::{Accuracy, HumanTime, Tense};
+                    HumanTime::from(chrono::Duration::milliseconds(-(number as i64)))
+                        .to_text_en(Accuracy::Precise, Tense::Present)
+                }
+            }
+            _ =>
+            {
+                match (value, spec.precision)
+                {
+                    (Value::Float(n), Some(precision)) => format!("{n:.precision$}"),
+                    (Value::Integer(n), Some(precision)) => format!("{:.precision$}", *n as f64),
+                    (Value::Text(s), Some(precision)) => s.chars().take(precision).collect(),
+                    _ => value.to_string(),
+                }
+            }
+        };
+
+        let s = match field.filter
+        {
+            Some(Filter::Upper) => s.to_uppercase(),
+            Some(Filter::Lower) => s.to_lowercase(),
+            _ => s,
+        };
+
+        let padding = spec.width.saturating_sub(display_width(&s));
+        let (before, after) = match spec.align
+        {
+            Align::Left => (0, padding),
+            Align::Right => (padding, 0),
+            Align::Center => (padding / 2, padding - padding / 2),
+        };
+        let fill = spec.fill.to_string();
+        Ok(format!("{}{s}{}", fill.repeat(before), fill.repeat(after)))
+    }
+}
+
+fn parse_line(index: usize, line: &str) -> Result<Vec<Piece>, TemplateError>
+{
+    let error = |message: &str, start, end| {
+        TemplateError::new(message.to_string(), index, line, start, end)
+    };
+
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let mut rest = line;
+    while let Some(position) = rest.find(['{', '}'])
+    {
+        let offset = line.len() - rest.len() + position;
+        text.push_str(&rest[..position]);
+
+        let escaped = if rest[position..].starts_with('{')
+        {
+            "{{"
+        }
+        else
+        {
+            "}}"
+        };
+        if rest[position..].starts_with(escaped)
+        {
+            text.push_str(&escaped[..1]);
+            rest = &rest[position + 2..];
+            continue;
+        }
+        if escaped == "}}"
+        {
+            return Err(error(
+                "unmatched `}` (write `}}` for a literal brace)",
+                offset,
+                offset + 1,
+            ));
+        }
+
+        let Some(len) = rest[position..].find('}')
+        else
+        {
+            return Err(error(
+                "unclosed `{` (write `{{` for a literal brace)",
+                offset,
+                line.len(),
+            ));
+        };
+
+        if !text.is_empty()
+        {
+            pieces.push(Piece::Text(std::mem::take(&mut text)));
+        }
+        pieces.push(Piece::Field(parse_field(
+            index,
+            line,
+            offset + 1,
+            offset + len,
+        )?));
+        rest = &rest[position + len + 1..];
+    }
+
+    text.push_str(rest);
+    if !text.is_empty()
+    {
+        pieces.push(Piece::Text(text));
+    }
+    Ok(pieces)
+}
+
+/// Parse the field in `line[start..end]`: `path[|filter][:spec]`
+fn parse_field(index: usize, line: &str, start: usize, end: usize) -> Result<Field, TemplateError>
+{
+    let error = |message: String, start, end| TemplateError::new(message, index, line, start, end);
+
+    let field = &line[start..end];
+    let (field, spec) = match field.find(':')
+    {
+        Some(colon) =>
+        {
+            (
+                &field[..colon],
+                Some((start + colon + 1, &field[colon + 1..])),
+            )
+        }
+        None => (field, None),
+    };
+    let (path, filter) = match field.find('|')
+    {
+        Some(bar) =>
+        {
+            (
+                &field[..bar],
+                Some((start + bar + 1, field[bar + 1..].trim())),
+            )
+        }
+        None => (field, None),
+    };
+
+    if path.is_empty()
+        || path.split('.').any(|name| {
+            name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
+    {
+        return Err(error(
+            format!("`{path}` isn't a field (e.g. `cpu.name`)"),
+            start,
+            start + path.len(),
+        ));
+    }
+
+    let filter = match filter
+    {
+        Some((position, name)) =>
+        {
+            Some(match name
+            {
+                "size" => Filter::Size,
+                "duration" => Filter::Duration,
+                "upper" => Filter::Upper,
+                "lower" => Filter::Lower,
+                _ =>
+                {
+                    let message =
+                        did_you_mean(format!("unknown filter `{name}`"), suggest(name, FILTERS));
+                    return Err(error(message, position, start + field.len()));
+                }
+            })
+        }
+        None => None,
+    };
+
+    let spec = match spec
+    {
+        Some((position, spec)) =>
+        {
+            parse_spec(spec).ok_or_else(|| {
+                error(
+                    format!(
+                        "invalid format spec `{spec}` (expected \
+                         `[[fill]align][width][.precision]`)"
+                    ),
+                    position,
+                    end,
+                )
+            })?
+        }
+        None => Spec::default(),
+    };
+
+    Ok(Field {
+        path: path.split('.').map(str::to_string).collect(),
+        filter,
+        spec,
+        line: index,
+        start,
+    })
+}
+
+/// Parse `[[fill]align][width][.precision]`
+fn parse_spec(s: &str) -> Option<Spec>
+{
+    let mut spec = Spec::default();
+    let align = |c| {
+        match c
+        {
+            '<' => Some(Align::Left),
+            '>' => Some(Align::Right),
+            '^' => Some(Align::Center),
+            _ => None,
+        }
+    };
+
+    let mut chars = s.chars();
+    let rest = match (chars.next(), chars.next().and_then(align))
+    {
+        (Some(fill), Some(alignment)) =>
+        {
+            spec.fill = fill;
+            spec.align = alignment;
+            chars.as_str()
+        }
+        (Some(c), None) =>
+        {
+            match align(c)
+            {
+                Some(alignment) =>
+                {
+                    spec.align = alignment;
+                    &s[c.len_utf8()..]
+                }
+                None => s,
+            }
+        }
+        (None, _) => s,
+    };
+
+    let (width, precision) = match rest.split_once('.')
+    {
+        Some((width, precision)) => (width, Some(precision)),
+        None => (rest, None),
+    };
+    if !width.is_empty()
+    {
+        spec.width = width.parse().ok()?;
+    }
+    if let Some(precision) = precision
+    {
+        spec.precision = Some(precision.parse().ok()?);
+    }
+    Some(spec)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn modules() -> Value
+    {
+        Value::Map(vec![
+            (
+                "cpu",
+                Value::Map(vec![
+                    ("name", "AMD Ryzen 5 5600X".into()),
+                    ("ghz", 4.384.into()),
+                    ("cores", 6u32.into()),
+                    ("limit", Value::None),
+                ]),
+            ),
+            ("memory", Value::Map(vec![("used", 1610612736u64.into())])),
+            (
+                "load",
+                Value::Map(vec![("average", vec![0.5, 1.25].into())]),
+            ),
+        ])
+    }
+
+    fn render(template: &str) -> Result<String, TemplateError>
+    {
+        Template::parse(template)?.render(&modules())
+    }
+
+    #[test]
+    fn renders_fields_filters_and_specs()
+    {
+        assert_eq!(
+            render("{cpu.name} @ {cpu.ghz:.1} GHz").unwrap(),
+            "AMD Ryzen 5 5600X @ 4.4 GHz"
+        );
+        assert_eq!(
+            render("RAM {memory.used|size}\n\n[{cpu.cores:>3}|{cpu.name|upper:-^7.3}]").unwrap(),
+            "RAM 1.50 GiB\n\n[  6|--AMD--]"
+        );
+        assert_eq!(
+            render("{{{load.average.1}}} {load.average}").unwrap(),
+            "{1.25} 0.50, 1.25"
+        );
+    }
+
+    #[test]
+    fn drops_lines_with_unavailable_fields()
+    {
+        assert_eq!(
+            render("CPU: {cpu.name}\nLIMIT: {cpu.limit}\nLOAD: {load.average.5}").unwrap(),
+            "CPU: AMD Ryzen 5 5600X"
+        );
+    }
+
+    #[test]
+    fn reports_errors_where_they_are()
+    {
+        let error = render("CPU: {cpu.name}\nRAM: {memory.usde|size}").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            concat!(
+                "`memory` has no field `usde`; did you mean `used`? (line 2, column 14)\n",
+                "    RAM: {memory.usde|size}\n",
+                "                 ^^^^"
+            )
+        );
+
+        let error = Template::parse("{cpu.name|uper}").unwrap_err();
+        assert_eq!(
+            error.message,
+            "unknown filter `uper`; did you mean `upper`?"
+        );
+        assert_eq!((error.line, error.column), (1, 11));
+
+        let error = Template::parse("ok\n  {cpu.name").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+
+        let error = Template::parse("{cpus.name}")
+            .unwrap()
+            .check(&["cpu", "memory"])
+            .unwrap_err();
+        assert_eq!(error.message, "unknown module `cpus`; did you mean `cpu`?");
+    }
+
+    #[test]
+    fn knows_which_fields_it_uses()
+    {
+        let template = Template::parse("{cpu.name}\n{load.average.1}").unwrap();
+        assert!(template.uses(&["cpu"]));
+        assert!(template.uses(&["load", "average"]));
+        assert!(!template.uses(&["cpu", "temperature"]));
+        assert!(!template.uses(&["memory"]));
+    }
+}